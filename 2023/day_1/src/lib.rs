@@ -0,0 +1,141 @@
+//! 2023 day 1: Trebuchet?!
+
+#![warn(missing_docs)]
+
+use aoc_common::{
+    arith::{self, Policy},
+    Answer, Error, Result, Solution,
+};
+
+/// The solution of the day's puzzle.
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 1;
+
+    type Input<'a> = Vec<&'a [u8]>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        Ok(input.lines().map(str::as_bytes).collect())
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer> {
+        calibration_sum(input, &STR_TO_DIGIT[9..], arith::policy()).map(Answer::from)
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer> {
+        calibration_sum(input, &STR_TO_DIGIT, arith::policy()).map(Answer::from)
+    }
+}
+
+/// Sums the two-digit calibration values of `lines`, made of the first and last of `digits` on
+/// each line.
+pub fn calibration_sum(lines: &[&[u8]], digits: &[(&[u8], u32)], policy: Policy) -> Result<i128> {
+    let mut result = 0;
+    for (index, line) in lines.iter().enumerate() {
+        let (Some(first_digit), Some(last_digit)) = (
+            find_first_digit(line, digits),
+            find_last_digit(line, digits),
+        ) else {
+            return Err(Error::NoSolution(format!("no digit on line {}", index + 1)));
+        };
+        let value = i128::from(first_digit * 10 + last_digit);
+        result = policy.add::<u32>(result, value, "sum of calibration values")?;
+    }
+    Ok(result)
+}
+
+/// Spelled-out digits followed by numerals, so `&STR_TO_DIGIT[9..]` holds only the numerals.
+pub const STR_TO_DIGIT: [(&[u8], u32); 19] = [
+    (b"one", 1),
+    (b"two", 2),
+    (b"three", 3),
+    (b"four", 4),
+    (b"five", 5),
+    (b"six", 6),
+    (b"seven", 7),
+    (b"eight", 8),
+    (b"nine", 9),
+    (b"0", 0),
+    (b"1", 1),
+    (b"2", 2),
+    (b"3", 3),
+    (b"4", 4),
+    (b"5", 5),
+    (b"6", 6),
+    (b"7", 7),
+    (b"8", 8),
+    (b"9", 9),
+];
+
+/// The value of the first of `digits` that appears in `line`.
+pub fn find_first_digit(line: &[u8], digits: &[(&[u8], u32)]) -> Option<u32> {
+    for index in 0..line.len() {
+        for (digit, value) in digits.iter() {
+            if line[index..].starts_with(digit) {
+                return Some(*value);
+            }
+        }
+    }
+    None
+}
+
+/// The value of the last of `digits` that appears in `line`, which may overlap the first.
+pub fn find_last_digit(line: &[u8], digits: &[(&[u8], u32)]) -> Option<u32> {
+    for index in (0..line.len()).rev() {
+        for (digit, value) in digits.iter() {
+            if line[index..].starts_with(digit) {
+                return Some(*value);
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{input::fixture, solution::answer, Part};
+
+    use super::*;
+
+    fn example_1() -> String {
+        fixture(env!("CARGO_MANIFEST_DIR"), "example_1.txt")
+    }
+
+    fn example_2() -> String {
+        fixture(env!("CARGO_MANIFEST_DIR"), "example_2.txt")
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(
+            answer::<Day>(&example_1(), Part::One),
+            Ok(Answer::Integer(142))
+        );
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(
+            answer::<Day>(&example_2(), Part::Two),
+            Ok(Answer::Integer(281))
+        );
+    }
+
+    #[test]
+    fn spelled_digits_overlap() {
+        assert_eq!(find_first_digit(b"eightwothree", &STR_TO_DIGIT), Some(8));
+        assert_eq!(find_last_digit(b"xtwone3four", &STR_TO_DIGIT), Some(4));
+        assert_eq!(find_last_digit(b"zoneight", &STR_TO_DIGIT), Some(8));
+        assert_eq!(find_first_digit(b"two", &STR_TO_DIGIT[9..]), None);
+    }
+
+    #[test]
+    fn line_without_digit() {
+        assert!(matches!(
+            answer::<Day>("abc", Part::One),
+            Err(Error::NoSolution(_))
+        ));
+    }
+}
@@ -0,0 +1,517 @@
+//! 2023 day 10: Pipe Maze.
+
+#![warn(missing_docs)]
+
+use aoc_common::{
+    geometry::Position,
+    random::Rng,
+    render::{Color, Picture},
+    Answer, Direction, Grid, ParseError, Part, Result, Solution,
+};
+
+pub mod reference;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PipeKind {
+    Vertical,
+    Horizontal,
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl PipeKind {
+    fn in_directions(&self) -> [Direction; 2] {
+        match self {
+            PipeKind::Vertical => [Direction::Up, Direction::Down],
+            PipeKind::Horizontal => [Direction::Left, Direction::Right],
+            PipeKind::TopLeft => [Direction::Up, Direction::Left],
+            PipeKind::TopRight => [Direction::Up, Direction::Right],
+            PipeKind::BottomLeft => [Direction::Down, Direction::Left],
+            PipeKind::BottomRight => [Direction::Down, Direction::Right],
+        }
+    }
+
+    /// The box-drawing character of the pipe.
+    fn symbol(&self) -> char {
+        match self {
+            PipeKind::Vertical => '│',
+            PipeKind::Horizontal => '─',
+            PipeKind::TopLeft => '┌',
+            PipeKind::TopRight => '┐',
+            PipeKind::BottomLeft => '└',
+            PipeKind::BottomRight => '┘',
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Trace {
+    in_direction: Direction,
+    out_direction: Direction,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Pipe {
+    kind: PipeKind,
+    trace: Option<Trace>,
+}
+
+/// The pipes on every tile, with the directions the loop goes through the ones on it once traced.
+pub struct Map {
+    pipes: Grid<Option<Pipe>>,
+}
+
+impl Map {
+    /// Returns the map, the starting position, and the first step of the loop from there.
+    pub fn parse(input: &str) -> Result<(Map, Position, Direction, Position), ParseError> {
+        let chars = Grid::parse(input, "pipe, '.' or 'S'", |c| {
+            "|-LJ7F.S".contains(c).then_some(c)
+        })?;
+        let pipes = chars.map(|&c| {
+            let kind = match c {
+                '|' => PipeKind::Vertical,
+                '-' => PipeKind::Horizontal,
+                'L' => PipeKind::BottomLeft,
+                'J' => PipeKind::BottomRight,
+                '7' => PipeKind::TopRight,
+                'F' => PipeKind::TopLeft,
+                '.' => return None,
+                // decide this later
+                'S' => PipeKind::Vertical,
+                other => unreachable!("{}", other),
+            };
+            Some(Pipe { kind, trace: None })
+        });
+
+        let mut map = Map { pipes };
+
+        let Some((starting_position, _)) = chars.enumerate().find(|(_, &c)| c == 'S') else {
+            return Err(ParseError::at(input, &input[input.len()..], "'S'"));
+        };
+        let start = &input[input.find('S').unwrap_or_default()..];
+
+        #[derive(Debug, Default)]
+        struct Info {
+            in_directions: Vec<Direction>,
+            trace_in_direction: Option<Direction>,
+            trace_out_direction: Option<Direction>,
+            next_position: Option<Position>,
+        }
+
+        impl Info {
+            fn add_out_connection(&mut self, out_direction: Direction, position: Position) {
+                self.in_directions.push(out_direction.opposite());
+                if self.next_position.is_none() {
+                    // First time
+                    self.next_position = Some(position);
+                    self.trace_out_direction = Some(out_direction);
+                } else {
+                    // Second time
+                    self.trace_in_direction = Some(out_direction.opposite());
+                }
+            }
+        }
+
+        let mut info = Info::default();
+        for out_direction in Direction::ALL {
+            if let Some(position) = map.pipes.step(starting_position, out_direction) {
+                if directions(&map, position, out_direction).is_some() {
+                    info.add_out_connection(out_direction, position);
+                }
+            }
+        }
+        if info.in_directions.len() != 2 {
+            return Err(ParseError::at(input, start, "'S' connected to two pipes"));
+        }
+        info.in_directions.sort();
+        let kind = match info.in_directions.as_slice() {
+            [Direction::Up, Direction::Down] => PipeKind::Vertical,
+            [Direction::Left, Direction::Right] => PipeKind::Horizontal,
+            [Direction::Up, Direction::Left] => PipeKind::TopLeft,
+            [Direction::Up, Direction::Right] => PipeKind::TopRight,
+            [Direction::Down, Direction::Left] => PipeKind::BottomLeft,
+            [Direction::Down, Direction::Right] => PipeKind::BottomRight,
+            _ => unreachable!(),
+        };
+        let starting_pipe = map.pipes[starting_position].as_mut().unwrap();
+        starting_pipe.kind = kind;
+
+        let trace = Trace {
+            in_direction: info.trace_in_direction.unwrap(),
+            out_direction: info.trace_out_direction.unwrap(),
+        };
+        starting_pipe.trace = Some(trace);
+
+        Ok((
+            map,
+            starting_position,
+            info.trace_out_direction.unwrap(),
+            info.next_position.unwrap(),
+        ))
+    }
+}
+
+/// Moves through the pipe at `through`, entered going `direction`. Returns the direction it leaves
+/// in and the next position, or `None` if the pipe doesn't connect or leads off the map.
+fn step(map: &Map, through: Position, direction: Direction) -> Option<(Direction, Position)> {
+    let (_, next_direction) = directions(map, through, direction)?;
+    let next = map.pipes.step(through, next_direction)?;
+    Some((next_direction, next))
+}
+
+/// Returns the directions going in and out of the pipe at `through` if it connects to a step going
+/// `direction`.
+fn directions(
+    map: &Map,
+    through: Position,
+    direction: Direction,
+) -> Option<(Direction, Direction)> {
+    let pipe = map.pipes[through]?;
+    let in_directions = pipe.kind.in_directions();
+    if direction == in_directions[0] {
+        Some((in_directions[0], in_directions[1].opposite()))
+    } else if direction == in_directions[1] {
+        Some((in_directions[1], in_directions[0].opposite()))
+    } else {
+        None
+    }
+}
+
+/// Traces the loop starting from `starting_position`, whose first step goes `direction` to
+/// `current`. Returns the position where the pipes stop connecting if they don't lead back.
+pub fn trace_loop(
+    map: &mut Map,
+    starting_position: Position,
+    mut direction: Direction,
+    mut current: Position,
+) -> Result<(), Position> {
+    loop {
+        let (out_direction, next) = step(map, current, direction).ok_or(current)?;
+        map.pipes[current].as_mut().unwrap().trace = Some(Trace {
+            in_direction: direction,
+            out_direction,
+        });
+
+        if next == starting_position {
+            return Ok(());
+        }
+        direction = out_direction;
+        current = next;
+    }
+}
+
+/// Generates a map of `size + 1` by `size + 1` tiles. The loop runs along the outline of a random
+/// region of `size` by `size` cells, through the tiles at the corners of the cells, and every tile
+/// off the loop holds a random pipe or ground.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let mut region = Grid::filled(size, size, false);
+    region[(rng.index(size), rng.index(size))] = true;
+    // Between a quarter and three quarters of the cells, unless the region can't grow that far.
+    let target = (size * size / 4 + rng.index(size * size / 2 + 1)).max(1);
+    let mut cells = 1;
+    for _ in 0..size * size * 10 {
+        if cells == target {
+            break;
+        }
+        let position = (rng.index(size), rng.index(size));
+        if region[position] || !region.neighbors4(position).any(|next| region[next]) {
+            continue;
+        }
+        region[position] = true;
+        if outline_is_loop(&region) {
+            cells += 1;
+        } else {
+            region[position] = false;
+        }
+    }
+
+    let mut tiles = Grid::filled(size + 1, size + 1, '.');
+    let mut on_loop = vec![];
+    for (row, col) in tiles.positions() {
+        let [top_left, top_right, bottom_left, bottom_right] = cells_around(&region, (row, col));
+        // The outline passes between cells of the region and cells outside.
+        let up = top_left != top_right;
+        let down = bottom_left != bottom_right;
+        let left = top_left != bottom_left;
+        let right = top_right != bottom_right;
+        tiles[(row, col)] = match (up, down, left, right) {
+            (true, true, false, false) => '|',
+            (false, false, true, true) => '-',
+            (true, false, false, true) => 'L',
+            (true, false, true, false) => 'J',
+            (false, true, true, false) => '7',
+            (false, true, false, true) => 'F',
+            _ => *rng.choose(&['|', '-', 'L', 'J', '7', 'F', '.']),
+        };
+        if up || down || left || right {
+            on_loop.push((row, col));
+        }
+    }
+
+    // No pipe off the loop may connect to the start.
+    let start = *rng.choose(&on_loop);
+    tiles[start] = 'S';
+    for position in tiles.neighbors4(start).collect::<Vec<_>>() {
+        if !on_loop.contains(&position) {
+            tiles[position] = '.';
+        }
+    }
+    tiles.to_string()
+}
+
+/// Whether the four cells meeting at the top left corner of cell `(row, col)` are in `region`, in
+/// reading order. Cells beyond the edges are not.
+fn cells_around(region: &Grid<bool>, (row, col): Position) -> [bool; 4] {
+    let cell = |row: usize, col: usize| region.get((row, col)).copied().unwrap_or(false);
+    [
+        row > 0 && col > 0 && cell(row - 1, col - 1),
+        row > 0 && cell(row - 1, col),
+        col > 0 && cell(row, col - 1),
+        cell(row, col),
+    ]
+}
+
+/// Whether the outline of `region`, which must be connected, is a single loop that doesn't touch
+/// itself: the region has no holes, and no two cells of it or outside it meet only at a corner.
+fn outline_is_loop(region: &Grid<bool>) -> bool {
+    for row in 0..=region.height() {
+        for col in 0..=region.width() {
+            let [top_left, top_right, bottom_left, bottom_right] = cells_around(region, (row, col));
+            if top_left == bottom_right && top_right == bottom_left && top_left != top_right {
+                return false;
+            }
+        }
+    }
+    // Flood the outside in from the edges. A cell neither in the region nor reached is in a hole.
+    let mut outside = region.map(|_| false);
+    let mut stack = region
+        .positions()
+        .filter(|&(row, col)| {
+            row == 0 || col == 0 || row + 1 == region.height() || col + 1 == region.width()
+        })
+        .collect::<Vec<_>>();
+    while let Some(position) = stack.pop() {
+        if region[position] || outside[position] {
+            continue;
+        }
+        outside[position] = true;
+        stack.extend(region.neighbors4(position));
+    }
+    region
+        .enumerate()
+        .all(|(position, &in_region)| in_region || outside[position])
+}
+
+/// The solution of the day's puzzle.
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 10;
+
+    /// The map with the loop traced.
+    type Input<'a> = Map;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        let (mut map, starting_position, direction, current) = Map::parse(input)?;
+        trace_loop(&mut map, starting_position, direction, current).map_err(|(row, col)| {
+            // The map parsed, so every line is made of one-byte tiles.
+            let line = input.lines().nth(row).unwrap_or_default();
+            ParseError::at(input, &line[col..], "pipe connected on both ends")
+        })?;
+        Ok(map)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer> {
+        Ok((loop_length(input) / 2).into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer> {
+        Ok(count_enclosed(input).into())
+    }
+
+    fn render(input: &Self::Input<'_>, part: Part) -> Result<Option<Picture>> {
+        Ok(Some(render(input, part)))
+    }
+}
+
+/// The pipes of `map`, with the loop highlighted and, for part 2, the enclosed tiles too.
+pub fn render(map: &Map, part: Part) -> Picture {
+    let symbols = map
+        .pipes
+        .map(|pipe| pipe.map_or('.', |pipe| pipe.kind.symbol()));
+    let on_loop = map
+        .pipes
+        .enumerate()
+        .filter(|(_, pipe)| is_in_loop(pipe.as_ref()))
+        .map(|(position, _)| position);
+    let picture = Picture::new(symbols).with_overlay("loop", Color::Cyan, on_loop);
+    match part {
+        Part::One => picture,
+        Part::Two => picture.with_overlay("enclosed", Color::Yellow, enclosed(map)),
+    }
+}
+
+/// The number of tiles on the traced loop of `map`.
+pub fn loop_length(map: &Map) -> usize {
+    map.pipes
+        .iter()
+        .filter(|pipe| is_in_loop(pipe.as_ref()))
+        .count()
+}
+
+/// The number of tiles enclosed by the traced loop of `map`.
+pub fn count_enclosed(map: &Map) -> usize {
+    enclosed(map).len()
+}
+
+/// The tiles enclosed by the traced loop of `map`, row by row.
+pub fn enclosed(map: &Map) -> Vec<Position> {
+    // Find which direction marks enter of inner region.
+    let enter_inner_direction = find_enter_inner_direction(map);
+
+    // Collect
+    let mut result = vec![];
+    for (row_index, row) in map.pipes.rows().enumerate() {
+        let mut inner = false;
+        for (col_index, pipe) in row.iter().enumerate() {
+            if inner && !is_in_loop(pipe.as_ref()) {
+                result.push((row_index, col_index));
+            }
+            if let Some(direction) = vertical_trace_direction(pipe.as_ref()) {
+                inner = direction == enter_inner_direction;
+            }
+        }
+    }
+    result
+}
+
+fn is_in_loop(pipe: Option<&Pipe>) -> bool {
+    pipe.and_then(|pipe| pipe.trace).is_some()
+}
+
+fn vertical_trace_direction(pipe: Option<&Pipe>) -> Option<Direction> {
+    let trace = pipe?.trace?;
+    if trace.in_direction.is_vertical() {
+        Some(trace.in_direction)
+    } else if trace.out_direction.is_vertical() {
+        Some(trace.out_direction)
+    } else {
+        None
+    }
+}
+
+fn find_enter_inner_direction(map: &Map) -> Direction {
+    for row in map.pipes.rows() {
+        for pipe in row {
+            if let Some(direction) = vertical_trace_direction(pipe.as_ref()) {
+                return direction;
+            }
+        }
+    }
+    unreachable!()
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{input::fixture, random, solution::answer, Part};
+
+    use super::*;
+
+    fn example_1() -> String {
+        fixture(env!("CARGO_MANIFEST_DIR"), "example_1.txt")
+    }
+
+    fn example_2() -> String {
+        fixture(env!("CARGO_MANIFEST_DIR"), "example_2.txt")
+    }
+
+    fn example_3() -> String {
+        fixture(env!("CARGO_MANIFEST_DIR"), "example_3.txt")
+    }
+
+    fn example_4() -> String {
+        fixture(env!("CARGO_MANIFEST_DIR"), "example_4.txt")
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(
+            answer::<Day>(&example_1(), Part::One),
+            Ok(Answer::Integer(8))
+        );
+    }
+
+    #[test]
+    fn part2_example_1() {
+        assert_eq!(
+            answer::<Day>(&example_2(), Part::Two),
+            Ok(Answer::Integer(4))
+        );
+    }
+
+    #[test]
+    fn part2_example_2() {
+        assert_eq!(
+            answer::<Day>(&example_3(), Part::Two),
+            Ok(Answer::Integer(8))
+        );
+    }
+
+    #[test]
+    fn part2_example_3() {
+        assert_eq!(
+            answer::<Day>(&example_4(), Part::Two),
+            Ok(Answer::Integer(10))
+        );
+    }
+
+    /// Mirrors a map along its main diagonal.
+    fn transpose(input: &str) -> String {
+        let tiles = Grid::parse(input, "tile", Some).unwrap();
+        tiles
+            .transpose()
+            .map(|&tile| match tile {
+                '|' => '-',
+                '-' => '|',
+                'L' => '7',
+                '7' => 'L',
+                other => other,
+            })
+            .to_string()
+    }
+
+    #[test]
+    fn generated_answers_survive_transposing() {
+        for seed in 0..50 {
+            let input = generate(&mut Rng::new(seed), 10);
+            for part in Part::ALL {
+                let expected = answer::<Day>(&input, part);
+                assert!(expected.is_ok(), "{}", input);
+                assert_eq!(
+                    answer::<Day>(&transpose(&input), part),
+                    expected,
+                    "{}",
+                    input
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn agrees_with_reference() {
+        for example in [example_2(), example_3(), example_4()] {
+            let map = Day::parse(&example).unwrap();
+            assert_eq!(count_enclosed(&map), reference::count_enclosed(&map));
+        }
+        random::assert_agree(
+            50,
+            |rng| generate(rng, 10),
+            |input| count_enclosed(&Day::parse(input).unwrap()),
+            |input| reference::count_enclosed(&Day::parse(input).unwrap()),
+        );
+    }
+}
@@ -0,0 +1,43 @@
+//! Slow but simple solutions to check the fast ones against.
+
+use aoc_common::Grid;
+
+use super::{is_in_loop, Map};
+
+/// Counts the enclosed tiles by flooding the outside of the loop. Every tile is blown up to 3 by 3
+/// cells with the pipe drawn through the middle, so that the flood can squeeze between pipes.
+pub fn count_enclosed(map: &Map) -> usize {
+    let (width, height) = (map.pipes.width(), map.pipes.height());
+    let mut walls = Grid::filled(width * 3, height * 3, false);
+    for ((row, col), pipe) in map.pipes.enumerate() {
+        let Some(pipe) = pipe.filter(|pipe| pipe.trace.is_some()) else {
+            continue;
+        };
+        let center = (row * 3 + 1, col * 3 + 1);
+        walls[center] = true;
+        for direction in pipe.kind.in_directions() {
+            let arm = walls.step(center, direction.opposite()).unwrap();
+            walls[arm] = true;
+        }
+    }
+
+    // Corner cells are never walls, so the flood starts from one.
+    let mut outside = walls.map(|_| false);
+    let mut stack = vec![(0, 0)];
+    outside[(0, 0)] = true;
+    while let Some(position) = stack.pop() {
+        for neighbor in walls.neighbors4(position).collect::<Vec<_>>() {
+            if !walls[neighbor] && !outside[neighbor] {
+                outside[neighbor] = true;
+                stack.push(neighbor);
+            }
+        }
+    }
+
+    map.pipes
+        .enumerate()
+        .filter(|&((row, col), pipe)| {
+            !is_in_loop(pipe.as_ref()) && !outside[(row * 3 + 1, col * 3 + 1)]
+        })
+        .count()
+}
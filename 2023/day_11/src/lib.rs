@@ -0,0 +1,203 @@
+//! 2023 day 11: Cosmic Expansion.
+
+#![warn(missing_docs)]
+
+use aoc_common::{
+    arith::{self, Policy},
+    render::{Color, Picture},
+    Answer, Grid, ParseError, Part, Result, Solution,
+};
+
+/// The galaxy image, with the rows and columns that hold no galaxy.
+pub struct Universe {
+    galaxies: Grid<bool>,
+    outer_expansions: Vec<usize>,
+    inner_expansions: Vec<usize>,
+}
+
+impl Universe {
+    /// Parses the image, noting the rows and columns without a galaxy.
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let galaxies = Grid::parse(input, "'#' or '.'", |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })?;
+        let outer_expansions = galaxies
+            .rows()
+            .enumerate()
+            .filter(|(_, row)| !row.contains(&true))
+            .map(|(index, _)| index)
+            .collect();
+        let inner_expansions = (0..galaxies.width())
+            .filter(|index| !galaxies.column(*index).any(|&galaxy| galaxy))
+            .collect();
+        Ok(Self {
+            galaxies,
+            outer_expansions,
+            inner_expansions,
+        })
+    }
+}
+
+/// The solution of the day's puzzle.
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 11;
+
+    type Input<'a> = Universe;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        Ok(Universe::parse(input)?)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer> {
+        sum_of_distances(input, 2, arith::policy()).map(Answer::from)
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer> {
+        sum_of_distances(input, 1_000_000, arith::policy()).map(Answer::from)
+    }
+
+    fn render(input: &Self::Input<'_>, _part: Part) -> Result<Option<Picture>> {
+        Ok(Some(render(input)))
+    }
+}
+
+/// The image with the galaxies and the expanded rows and columns highlighted. Both parts expand
+/// the same rows and columns, only by different factors.
+pub fn render(universe: &Universe) -> Picture {
+    let symbols = universe
+        .galaxies
+        .map(|&galaxy| if galaxy { '#' } else { '.' });
+    let expanded = universe
+        .galaxies
+        .enumerate()
+        .filter(|((row, col), _)| {
+            universe.outer_expansions.contains(row) || universe.inner_expansions.contains(col)
+        })
+        .map(|(position, _)| position);
+    let galaxies = universe
+        .galaxies
+        .enumerate()
+        .filter(|(_, &galaxy)| galaxy)
+        .map(|(position, _)| position);
+    Picture::new(symbols)
+        .with_overlay("expanded", Color::Blue, expanded)
+        .with_overlay("galaxies", Color::Yellow, galaxies)
+}
+
+/// Sums the distances between all pairs of galaxies, where each empty row or column counts as
+/// `expansion_factor` ones. Distances are `usize`s under `policy`.
+pub fn sum_of_distances(
+    universe: &Universe,
+    expansion_factor: usize,
+    policy: Policy,
+) -> Result<i128> {
+    let positions = universe
+        .galaxies
+        .enumerate()
+        .filter(|(_, &cell)| cell)
+        .map(|(position, _)| position)
+        .collect::<Vec<_>>();
+
+    let mut result = 0;
+    for (index, position) in positions.iter().enumerate() {
+        for other in &positions[index + 1..] {
+            let (outer, inner) = (&universe.outer_expansions, &universe.inner_expansions);
+            let rows = distance(position.0, other.0, outer, expansion_factor, policy)?;
+            let cols = distance(position.1, other.1, inner, expansion_factor, policy)?;
+            let distance = policy.add::<usize>(rows, cols, "distance")?;
+            result = policy.add::<usize>(result, distance, "sum of distances")?;
+        }
+    }
+    Ok(result)
+}
+
+fn distance(
+    a: usize,
+    b: usize,
+    expansions: &[usize],
+    expansion_factor: usize,
+    policy: Policy,
+) -> Result<i128> {
+    if a < b {
+        distance(b, a, expansions, expansion_factor, policy)
+    } else {
+        let expansion = expansions
+            .iter()
+            .filter(|&&index| index < a && index > b)
+            .count();
+        let factor = expansion_factor as i128 - 1;
+        let extra = policy.mul::<usize>(expansion as i128, factor, "expansion")?;
+        policy.add::<usize>((a - b) as i128, extra, "distance")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{input::fixture, solution::answer, Error, Part};
+
+    use super::*;
+
+    fn example() -> String {
+        fixture(env!("CARGO_MANIFEST_DIR"), "example.txt")
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(
+            answer::<Day>(&example(), Part::One),
+            Ok(Answer::Integer(374))
+        );
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(
+            answer::<Day>(&example(), Part::Two),
+            Ok(Answer::Integer(82000210))
+        );
+    }
+
+    #[test]
+    fn render_example() {
+        let universe = Day::parse(&example()).unwrap();
+        let picture = render(&universe);
+        let cells = picture
+            .overlays
+            .iter()
+            .map(|overlay| (overlay.name.as_str(), overlay.cells.len()))
+            .collect::<Vec<_>>();
+        assert_eq!(cells, [("expanded", 44), ("galaxies", 9)]);
+        assert_eq!(picture.cells.to_string(), example());
+    }
+
+    #[test]
+    fn other_expansion_factors() {
+        let input = example();
+        let universe = Day::parse(&input).unwrap();
+        assert_eq!(sum_of_distances(&universe, 10, Policy::Checked), Ok(1030));
+        assert_eq!(sum_of_distances(&universe, 100, Policy::Checked), Ok(8410));
+    }
+
+    #[test]
+    fn overflowing_distances() {
+        let universe = Day::parse("#.\n..\n.#\n").unwrap();
+        assert_eq!(
+            sum_of_distances(&universe, usize::MAX, Policy::Checked),
+            Err(Error::Overflow("distance".to_string()))
+        );
+        let max = i128::from(usize::MAX as u64);
+        assert_eq!(
+            sum_of_distances(&universe, usize::MAX, Policy::Saturating),
+            Ok(max)
+        );
+        assert_eq!(
+            sum_of_distances(&universe, usize::MAX, Policy::Wide),
+            Ok(max + 2)
+        );
+    }
+}
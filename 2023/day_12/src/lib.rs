@@ -0,0 +1,377 @@
+//! 2023 day 12: Hot Springs.
+
+#![warn(missing_docs)]
+
+use aoc_common::{
+    arith::{self, Policy},
+    parallel, parse,
+    random::Rng,
+    Answer, ParseError, Result, Solution,
+};
+
+pub mod reference;
+
+/// A row of springs, each damaged, operational or unknown, and the sizes of its damaged groups.
+#[derive(Debug)]
+pub struct Puzzle {
+    /// Whether each spring is damaged, or `None` if unknown.
+    pub damaged: Vec<Option<bool>>,
+    /// The sizes of the groups of damaged springs, in order.
+    pub segments: Vec<usize>,
+}
+
+impl Puzzle {
+    /// Repeats the springs five times separated by unknowns, and the segments five times.
+    pub fn unfold(&self) -> Puzzle {
+        let damaged = vec![self.damaged.clone(); 5].join(&None);
+        let segments = vec![self.segments.clone(); 5]
+            .into_iter()
+            .flatten()
+            .collect();
+        Puzzle { damaged, segments }
+    }
+}
+
+/// Counts the ways to fill in the unknown springs so that the damaged groups match the segments.
+pub fn solve(puzzle: &Puzzle, policy: Policy) -> Result<i128> {
+    let num_damaged = puzzle
+        .segments
+        .iter()
+        .try_fold(0usize, |sum, &segment| sum.checked_add(segment))
+        .filter(|&num_damaged| num_damaged <= puzzle.damaged.len());
+    // More damaged springs than springs can't be arranged, and would only waste memory.
+    let Some(num_damaged) = num_damaged else {
+        return Ok(0);
+    };
+    // An empty row has a single arrangement, as long as it needs no damaged spring.
+    if puzzle.damaged.is_empty() {
+        return Ok(i128::from(num_damaged == 0));
+    }
+    // Outer: index of `damaged`.
+    // Middle: 0 -> false, 1 -> true.
+    // Inner: number of damaged, with a spare slot for one more than needed, which is never read.
+    let mut dp =
+        vec![[vec![0i128; num_damaged + 2], vec![0; num_damaged + 2],]; puzzle.damaged.len()];
+    let add = |count: &mut i128, more: i128| -> Result<()> {
+        *count = policy.add::<usize>(*count, more, "arrangements")?;
+        Ok(())
+    };
+
+    match puzzle.damaged[0] {
+        Some(true) => dp[0][1][1] = 1,
+        Some(false) => dp[0][0][0] = 1,
+        None => {
+            dp[0][0][0] = 1;
+            dp[0][1][1] = 1;
+        }
+    }
+
+    for (index, &current) in puzzle.damaged.iter().enumerate().skip(1) {
+        for num_damaged in 0..=num_damaged {
+            for last_is_damaged in [false, true] {
+                let Some(expected) = find_expected(num_damaged, last_is_damaged, &puzzle.segments)
+                else {
+                    continue;
+                };
+                let last_is_damaged = if last_is_damaged { 1 } else { 0 };
+                match (current, expected) {
+                    (Some(true), None | Some(true)) | (None, Some(true)) => {
+                        let count = dp[index - 1][last_is_damaged][num_damaged];
+                        add(&mut dp[index][1][num_damaged + 1], count)?;
+                    }
+                    (Some(false), None | Some(false)) | (None, Some(false)) => {
+                        let count = dp[index - 1][last_is_damaged][num_damaged];
+                        add(&mut dp[index][0][num_damaged], count)?;
+                    }
+                    (None, None) => {
+                        let count = dp[index - 1][last_is_damaged][num_damaged];
+                        add(&mut dp[index][0][num_damaged], count)?;
+                        add(&mut dp[index][1][num_damaged + 1], count)?;
+                    }
+                    (Some(true), Some(false)) | (Some(false), Some(true)) => {
+                        continue;
+                    }
+                }
+            }
+        }
+    }
+
+    let last = &dp[puzzle.damaged.len() - 1];
+    policy.add::<usize>(last[0][num_damaged], last[1][num_damaged], "arrangements")
+}
+
+/// Whether the next spring must be damaged, after `num_damaged` damaged springs of which the last
+/// one is `last_is_damaged`:
+///
+/// - `None`: Impossible
+/// - `Some(None)`: Arbitrary
+/// - `Some(Some)`: Fixed
+pub fn find_expected(
+    mut num_damaged: usize,
+    last_is_damaged: bool,
+    segments: &[usize],
+) -> Option<Option<bool>> {
+    let mut index = 0;
+    while index < segments.len() && num_damaged >= segments[index] {
+        num_damaged -= segments[index];
+        index += 1;
+    }
+
+    if index == segments.len() {
+        if num_damaged == 0 {
+            Some(Some(false))
+        } else {
+            None
+        }
+    } else {
+        if num_damaged == 0 {
+            if last_is_damaged {
+                Some(Some(false))
+            } else {
+                Some(None)
+            }
+        } else {
+            if last_is_damaged {
+                Some(Some(true))
+            } else {
+                None
+            }
+        }
+    }
+}
+
+/// Parses something like "???.### 1,1,3".
+pub fn parse(input: &str, line: &str) -> Result<Puzzle, ParseError> {
+    let (springs, segments) = parse::split_once(input, line, " ")?;
+    if springs.is_empty() {
+        return Err(ParseError::at(input, springs, "'#', '.' or '?'"));
+    }
+    let damaged = springs
+        .char_indices()
+        .map(|(index, x)| match x {
+            '#' => Ok(Some(true)),
+            '.' => Ok(Some(false)),
+            '?' => Ok(None),
+            _ => Err(ParseError::at(input, &springs[index..], "'#', '.' or '?'")),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let segments = segments
+        .split(',')
+        .map(|x| match parse::number(input, x)? {
+            0 => Err(ParseError::at(input, x, "a positive group size")),
+            segment => Ok(segment),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Puzzle { damaged, segments })
+}
+
+/// Generates `size` rows of up to `size` springs. Every row is made from an actual arrangement
+/// with some springs hidden, so it has at least one arrangement.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let mut result = String::new();
+    for _ in 0..size {
+        let len = 1 + rng.index(size);
+        let mut springs = (0..len).map(|_| rng.chance(1, 2)).collect::<Vec<_>>();
+        springs[rng.index(len)] = true;
+        let segments = springs
+            .split(|&damaged| !damaged)
+            .filter(|segment| !segment.is_empty())
+            .map(|segment| segment.len().to_string())
+            .collect::<Vec<_>>();
+        for damaged in springs {
+            result.push(match (rng.chance(1, 2), damaged) {
+                (true, _) => '?',
+                (false, true) => '#',
+                (false, false) => '.',
+            });
+        }
+        result += &format!(" {}\n", segments.join(","));
+    }
+    result
+}
+
+/// The solution of the day's puzzle.
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 12;
+
+    type Input<'a> = Vec<Puzzle>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        Ok(input
+            .lines()
+            .map(|line| parse(input, line))
+            .collect::<Result<_, _>>()?)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer> {
+        let policy = arith::policy();
+        let counts = parallel::map(input, |puzzle| solve(puzzle, policy));
+        sum_of_arrangements(counts, policy).map(Answer::from)
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer> {
+        let policy = arith::policy();
+        let counts = parallel::map(input, |puzzle| solve(&puzzle.unfold(), policy));
+        sum_of_arrangements(counts, policy).map(Answer::from)
+    }
+}
+
+/// Sums the arrangements of every row, failing with the first row that failed.
+fn sum_of_arrangements(counts: Vec<Result<i128>>, policy: Policy) -> Result<i128> {
+    let counts = counts.into_iter().collect::<Result<Vec<_>>>()?;
+    policy.sum::<usize>(counts, "sum of arrangements")
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{input::fixture, random, solution::answer, Error, Part};
+
+    use super::*;
+
+    fn count(puzzle: &Puzzle) -> usize {
+        solve(puzzle, Policy::Checked).unwrap() as usize
+    }
+
+    fn example() -> String {
+        fixture(env!("CARGO_MANIFEST_DIR"), "example.txt")
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(
+            answer::<Day>(&example(), Part::One),
+            Ok(Answer::Integer(21))
+        );
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(
+            answer::<Day>(&example(), Part::Two),
+            Ok(Answer::Integer(525152))
+        );
+    }
+
+    #[test]
+    fn arrangements_per_line() {
+        let input = example();
+        let puzzles = Day::parse(&input).unwrap();
+        let counts = puzzles.iter().map(count).collect::<Vec<_>>();
+        assert_eq!(counts, [1, 4, 1, 1, 4, 10]);
+        let counts = puzzles
+            .iter()
+            .map(|puzzle| count(&puzzle.unfold()))
+            .collect::<Vec<_>>();
+        assert_eq!(counts, [1, 16384, 1, 16, 2500, 506250]);
+    }
+
+    #[test]
+    fn expected_next_spring() {
+        let segments = [1, 1, 3];
+        assert_eq!(find_expected(0, false, &segments), Some(None));
+        assert_eq!(find_expected(1, true, &segments), Some(Some(false)));
+        assert_eq!(find_expected(1, false, &segments), Some(None));
+        assert_eq!(find_expected(3, true, &segments), Some(Some(true)));
+        assert_eq!(find_expected(3, false, &segments), None);
+        assert_eq!(find_expected(5, true, &segments), Some(Some(false)));
+        assert_eq!(find_expected(6, true, &segments), None);
+    }
+
+    #[test]
+    fn overflowing_arrangements() {
+        // One damaged spring fits 64 unknown ones in 64 ways, but 32 fit 130 in C(99, 32) ways.
+        let puzzle = Puzzle {
+            damaged: vec![None; 64],
+            segments: vec![1],
+        };
+        assert_eq!(solve(&puzzle, Policy::Checked), Ok(64));
+        let puzzle = Puzzle {
+            damaged: vec![None; 130],
+            segments: vec![1; 32],
+        };
+        let overflow = Err(Error::Overflow("arrangements".to_string()));
+        assert_eq!(solve(&puzzle, Policy::Checked), overflow);
+        assert_eq!(
+            solve(&puzzle, Policy::Saturating),
+            Ok(i128::from(usize::MAX as u64))
+        );
+        assert!(solve(&puzzle, Policy::Wide).unwrap() > i128::from(usize::MAX as u64));
+    }
+
+    #[test]
+    fn more_damaged_than_springs() {
+        let input = "??? 2,2\n? 18446744073709551615,1\n";
+        assert_eq!(answer::<Day>(input, Part::One), Ok(Answer::Integer(0)));
+    }
+
+    #[test]
+    fn empty_rows_are_rejected() {
+        let input = "# 1\n 1\n";
+        let error = parse(input, &input[4..6]).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert!(Day::parse(input).is_err());
+    }
+
+    #[test]
+    fn empty_groups_are_rejected() {
+        let input = "# 0\n";
+        let error = parse(input, &input[..3]).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 3));
+        assert_eq!(error.expected, "a positive group size");
+    }
+
+    #[test]
+    fn rows_without_damaged_springs() {
+        let row = |damaged: Vec<Option<bool>>, segments: Vec<usize>| {
+            solve(&Puzzle { damaged, segments }, Policy::Checked)
+        };
+        assert_eq!(row(vec![], vec![]), Ok(1));
+        assert_eq!(row(vec![], vec![1]), Ok(0));
+        assert_eq!(row(vec![Some(false), None], vec![]), Ok(1));
+        assert_eq!(row(vec![Some(true)], vec![]), Ok(0));
+        assert_eq!(row(vec![Some(true)], vec![0]), Ok(0));
+    }
+
+    #[test]
+    fn generated_rows_have_arrangements() {
+        for seed in 0..50 {
+            let input = generate(&mut Rng::new(seed), 8);
+            for puzzle in Day::parse(&input).unwrap() {
+                let arrangements = count(&puzzle);
+                assert!(arrangements >= 1, "{}", input);
+                // Every copy can be arranged on its own, with the unknowns between operational.
+                assert!(count(&puzzle.unfold()) >= arrangements.pow(5), "{}", input);
+            }
+        }
+    }
+
+    #[test]
+    fn agrees_with_reference() {
+        let sum = |input: &str, count: fn(&Puzzle) -> usize, unfold: bool| {
+            Day::parse(input)
+                .unwrap()
+                .iter()
+                .map(|puzzle| match unfold {
+                    true => count(&puzzle.unfold()),
+                    false => count(puzzle),
+                })
+                .sum::<usize>()
+        };
+        random::assert_agree(
+            50,
+            |rng| generate(rng, 10),
+            |input| sum(input, count, false),
+            |input| sum(input, reference::count_arrangements, false),
+        );
+        random::assert_agree(
+            20,
+            |rng| generate(rng, 2),
+            |input| sum(input, count, true),
+            |input| sum(input, reference::count_arrangements, true),
+        );
+    }
+}
@@ -0,0 +1,34 @@
+//! Slow but simple solutions to check the fast ones against.
+
+use super::Puzzle;
+
+/// Counts the arrangements by trying every way to fill in the unknown springs. Only for rows with
+/// a few unknowns.
+pub fn count_arrangements(puzzle: &Puzzle) -> usize {
+    let unknowns = puzzle
+        .damaged
+        .iter()
+        .enumerate()
+        .filter(|(_, damaged)| damaged.is_none())
+        .map(|(index, _)| index)
+        .collect::<Vec<_>>();
+    (0..1u64 << unknowns.len())
+        .filter(|assignment| {
+            let mut springs = puzzle.damaged.clone();
+            for (bit, &index) in unknowns.iter().enumerate() {
+                springs[index] = Some(assignment >> bit & 1 == 1);
+            }
+            let springs = springs.into_iter().flatten().collect::<Vec<_>>();
+            groups(&springs) == puzzle.segments
+        })
+        .count()
+}
+
+/// The sizes of the groups of damaged springs.
+fn groups(springs: &[bool]) -> Vec<usize> {
+    springs
+        .split(|&damaged| !damaged)
+        .filter(|group| !group.is_empty())
+        .map(<[bool]>::len)
+        .collect()
+}
@@ -0,0 +1,225 @@
+//! 2023 day 13: Point of Incidence.
+
+#![warn(missing_docs)]
+
+use aoc_common::{
+    arith::{self, Policy},
+    geometry::Position,
+    parallel,
+    render::{Color, Picture},
+    Answer, Error, Grid, ParseError, Part, Result, Solution,
+};
+
+/// A pattern of ash and rocks, with `true` for rocks.
+pub struct Puzzle {
+    /// The cells of the pattern.
+    pub pattern: Grid<bool>,
+}
+
+impl Puzzle {
+    /// Parses the pattern in `section`, a slice of `input`, which must have at least one cell.
+    pub fn parse(input: &str, section: &str) -> Result<Puzzle, ParseError> {
+        if section.trim().is_empty() {
+            return Err(ParseError::at(input, section, "a pattern"));
+        }
+        let pattern = Grid::parse_within(input, section, "'#' or '.'", |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })?;
+        Ok(Puzzle { pattern })
+    }
+}
+
+/// Returns if `pattern` reflects across the horizontal line before row `position` with exactly
+/// `smudges` mismatching cells.
+pub fn is_horizontally_reflected(pattern: &Grid<bool>, position: usize, smudges: usize) -> bool {
+    let mut diff = 0;
+    for top in 0..position {
+        let bottom = 2 * position - top - 1;
+        if bottom < pattern.height() {
+            let top_row = pattern.row(top);
+            let bottom_row = pattern.row(bottom);
+            diff += top_row
+                .iter()
+                .zip(bottom_row)
+                .filter(|(top, bottom)| top != bottom)
+                .count();
+            if diff > smudges {
+                return false;
+            }
+        }
+    }
+    diff == smudges
+}
+
+/// The first row that a horizontal reflection line with `smudges` mismatches goes before.
+pub fn find_horizontal_reflection(pattern: &Grid<bool>, smudges: usize) -> Option<usize> {
+    (1..pattern.height()).find(|&index| is_horizontally_reflected(pattern, index, smudges))
+}
+
+/// Summarizes the reflection line with `smudges` mismatches: 100 times the number of rows above a
+/// horizontal one, or the number of columns left of a vertical one.
+pub fn solve(puzzle: &Puzzle, smudges: usize) -> Option<usize> {
+    find_horizontal_reflection(&puzzle.pattern, smudges)
+        .map(|index| index * 100)
+        // A vertical reflection line is a horizontal one of the transposed pattern.
+        .or_else(|| find_horizontal_reflection(&puzzle.pattern.transpose(), smudges))
+}
+
+/// Sums the summaries of every pattern, failing if a pattern has no reflection line. The sum is a
+/// `usize` under `policy`.
+pub fn summarize(puzzles: &[Puzzle], smudges: usize, policy: Policy) -> Result<i128> {
+    let mut result = 0;
+    let lines = parallel::map(puzzles, |puzzle| solve(puzzle, smudges));
+    for (index, line) in lines.into_iter().enumerate() {
+        let line = line.ok_or_else(|| {
+            Error::NoSolution(format!("pattern {} has no reflection line", index + 1))
+        })?;
+        result = policy.add::<usize>(result, line as i128, "sum of summaries")?;
+    }
+    Ok(result)
+}
+
+/// The cells on both sides of the reflection line with `smudges` mismatches, if there is one.
+pub fn mirror_line(pattern: &Grid<bool>, smudges: usize) -> Vec<Position> {
+    if let Some(row) = find_horizontal_reflection(pattern, smudges) {
+        (0..pattern.width())
+            .flat_map(|col| [(row - 1, col), (row, col)])
+            .collect()
+    } else if let Some(col) = find_horizontal_reflection(&pattern.transpose(), smudges) {
+        (0..pattern.height())
+            .flat_map(|row| [(row, col - 1), (row, col)])
+            .collect()
+    } else {
+        vec![]
+    }
+}
+
+/// The patterns one below the other, separated by an empty row, with the reflection line with
+/// `smudges` mismatches of each highlighted.
+pub fn render(puzzles: &[Puzzle], smudges: usize) -> Picture {
+    let width = puzzles
+        .iter()
+        .map(|puzzle| puzzle.pattern.width())
+        .max()
+        .unwrap_or(0);
+    let height = puzzles
+        .iter()
+        .map(|puzzle| puzzle.pattern.height() + 1)
+        .sum::<usize>()
+        .saturating_sub(1);
+    let mut symbols = Grid::filled(width, height, ' ');
+    let mut lines = vec![];
+    let mut top = 0;
+    for puzzle in puzzles {
+        for ((row, col), &rock) in puzzle.pattern.enumerate() {
+            symbols[(top + row, col)] = if rock { '#' } else { '.' };
+        }
+        let line = mirror_line(&puzzle.pattern, smudges);
+        lines.extend(line.into_iter().map(|(row, col)| (top + row, col)));
+        top += puzzle.pattern.height() + 1;
+    }
+    Picture::new(symbols).with_overlay("mirror line", Color::Cyan, lines)
+}
+
+/// The solution of the day's puzzle.
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 13;
+
+    type Input<'a> = Vec<Puzzle>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        // Blank lines after the last pattern don't start another one.
+        Ok(input
+            .trim_end()
+            .split("\n\n")
+            .map(|section| Puzzle::parse(input, section))
+            .collect::<Result<_, _>>()?)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer> {
+        summarize(input, 0, arith::policy()).map(Answer::from)
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer> {
+        summarize(input, 1, arith::policy()).map(Answer::from)
+    }
+
+    fn render(input: &Self::Input<'_>, part: Part) -> Result<Option<Picture>> {
+        let smudges = match part {
+            Part::One => 0,
+            Part::Two => 1,
+        };
+        Ok(Some(render(input, smudges)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{input::fixture, solution::answer, Part};
+
+    use super::*;
+
+    fn example() -> String {
+        fixture(env!("CARGO_MANIFEST_DIR"), "example.txt")
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(
+            answer::<Day>(&example(), Part::One),
+            Ok(Answer::Integer(405))
+        );
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(
+            answer::<Day>(&example(), Part::Two),
+            Ok(Answer::Integer(400))
+        );
+    }
+
+    #[test]
+    fn reflection_lines() {
+        let input = example();
+        let puzzles = Day::parse(&input).unwrap();
+        assert_eq!(solve(&puzzles[0], 0), Some(5));
+        assert_eq!(solve(&puzzles[1], 0), Some(400));
+        assert_eq!(solve(&puzzles[0], 1), Some(300));
+        assert_eq!(solve(&puzzles[1], 1), Some(100));
+        assert_eq!(summarize(&puzzles, 1, Policy::Checked), Ok(400));
+    }
+
+    #[test]
+    fn blank_lines() {
+        let input = example() + "\n\n";
+        assert_eq!(answer::<Day>(&input, Part::One), Ok(Answer::Integer(405)));
+        for input in ["", "\n", "#.\n\n\n\n#.\n"] {
+            assert!(
+                matches!(Day::parse(input), Err(Error::Parse(_))),
+                "{:?} parsed",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn render_example() {
+        let input = example();
+        let puzzles = Day::parse(&input).unwrap();
+        let picture = render(&puzzles, 0);
+        assert_eq!(
+            picture.cells.to_string(),
+            input.trim_end().replace("\n\n", "\n         \n") + "\n"
+        );
+        let lines = &picture.overlays[0].cells;
+        assert_eq!(lines.len(), 2 * 7 + 2 * 9);
+        assert!(lines.contains(&(0, 4)) && lines.contains(&(6, 5)));
+        assert!(lines.contains(&(11, 0)) && lines.contains(&(12, 8)));
+    }
+}
@@ -0,0 +1,430 @@
+//! 2023 day 14: Parabolic Reflector Dish.
+
+#![warn(missing_docs)]
+
+use std::fmt::{Display, Formatter};
+
+use aoc_common::{
+    cycle::{self, Mode},
+    render::{Color, Picture},
+    trace::{Ignore, Observer, Tracer},
+    Answer, Direction, Grid, ParseError, Part, Result, Solution,
+};
+
+/// What is at a location of the platform.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Occupance {
+    /// Nothing, `.`.
+    Empty,
+    /// A round rock, `O`, which rolls.
+    Round,
+    /// A cube-shaped rock, `#`, which stays put.
+    Cube,
+}
+
+impl Occupance {
+    /// The character the location is parsed from.
+    pub fn symbol(self) -> char {
+        match self {
+            Occupance::Empty => '.',
+            Occupance::Round => 'O',
+            Occupance::Cube => '#',
+        }
+    }
+}
+
+impl Display for Occupance {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.symbol())
+    }
+}
+
+/// The platform with its round and cube-shaped rocks.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Puzzle {
+    /// What is at every location.
+    pub locations: Grid<Occupance>,
+}
+
+impl Puzzle {
+    fn col_north(&mut self, col: usize) {
+        let mut next_position = 0;
+        for row in 0..self.locations.height() {
+            match self.locations[(row, col)] {
+                Occupance::Round => {
+                    if next_position != row {
+                        self.locations[(next_position, col)] = Occupance::Round;
+                        self.locations[(row, col)] = Occupance::Empty;
+                    }
+                    next_position += 1;
+                }
+                Occupance::Cube => {
+                    next_position = row + 1;
+                }
+                Occupance::Empty => {}
+            }
+        }
+    }
+
+    /// Tilts the platform north, rolling every round rock as far as it goes.
+    pub fn north(&mut self) {
+        for col in 0..self.locations.width() {
+            self.col_north(col);
+        }
+    }
+
+    fn col_south(&mut self, col: usize) {
+        let mut next_position = self.locations.height().saturating_sub(1);
+        for row in (0..self.locations.height()).rev() {
+            match self.locations[(row, col)] {
+                Occupance::Round => {
+                    if next_position != row {
+                        self.locations[(next_position, col)] = Occupance::Round;
+                        self.locations[(row, col)] = Occupance::Empty;
+                    }
+                    // Saturating, as nothing is left to place once the top row is reached.
+                    next_position = next_position.saturating_sub(1);
+                }
+                Occupance::Cube => {
+                    next_position = row.saturating_sub(1);
+                }
+                Occupance::Empty => {}
+            }
+        }
+    }
+
+    /// Tilts the platform south, rolling every round rock as far as it goes.
+    pub fn south(&mut self) {
+        for col in 0..self.locations.width() {
+            self.col_south(col);
+        }
+    }
+
+    fn row_west(&mut self, row: usize) {
+        let mut next_position = 0;
+        for col in 0..self.locations.width() {
+            match self.locations[(row, col)] {
+                Occupance::Round => {
+                    if next_position != col {
+                        self.locations[(row, next_position)] = Occupance::Round;
+                        self.locations[(row, col)] = Occupance::Empty;
+                    }
+                    next_position += 1;
+                }
+                Occupance::Cube => {
+                    next_position = col + 1;
+                }
+                Occupance::Empty => {}
+            }
+        }
+    }
+
+    /// Tilts the platform west, rolling every round rock as far as it goes.
+    pub fn west(&mut self) {
+        for row in 0..self.locations.height() {
+            self.row_west(row);
+        }
+    }
+
+    fn row_east(&mut self, row: usize) {
+        let mut next_position = self.locations.width().saturating_sub(1);
+        for col in (0..self.locations.width()).rev() {
+            match self.locations[(row, col)] {
+                Occupance::Round => {
+                    if next_position != col {
+                        self.locations[(row, next_position)] = Occupance::Round;
+                        self.locations[(row, col)] = Occupance::Empty;
+                    }
+                    // Saturating, as nothing is left to place once the left column is reached.
+                    next_position = next_position.saturating_sub(1);
+                }
+                Occupance::Cube => {
+                    next_position = col.saturating_sub(1);
+                }
+                Occupance::Empty => {}
+            }
+        }
+    }
+
+    /// Tilts the platform east, rolling every round rock as far as it goes.
+    pub fn east(&mut self) {
+        for row in 0..self.locations.height() {
+            self.row_east(row);
+        }
+    }
+
+    /// Tilts the platform so that round rocks roll towards `direction`, where up is north.
+    pub fn tilt(&mut self, direction: Direction) {
+        match direction {
+            Direction::Up => self.north(),
+            Direction::Down => self.south(),
+            Direction::Left => self.west(),
+            Direction::Right => self.east(),
+        }
+    }
+
+    fn col_north_load(&self, col: usize) -> usize {
+        let mut result = 0;
+        for row in 0..self.locations.height() {
+            if let Occupance::Round = self.locations[(row, col)] {
+                result += self.locations.height() - row;
+            }
+        }
+        result
+    }
+
+    /// Tilts the platform north, west, south and east.
+    pub fn spin_cycle(&mut self) {
+        self.spin_cycle_traced(&mut Ignore);
+    }
+
+    /// Like [`Puzzle::spin_cycle`], reporting every tilt to `observer`.
+    pub fn spin_cycle_traced(&mut self, observer: &mut impl for<'a> Observer<Event<'a>>) {
+        for direction in [
+            Direction::Up,
+            Direction::Left,
+            Direction::Down,
+            Direction::Right,
+        ] {
+            self.tilt(direction);
+            observer.observe(&Event::Tilt {
+                direction,
+                platform: self,
+            });
+        }
+    }
+
+    /// The total load on the north support beams.
+    pub fn north_load(&self) -> usize {
+        let mut result = 0;
+        for col in 0..self.locations.width() {
+            result += self.col_north_load(col);
+        }
+        result
+    }
+
+    /// Parses the platform, one location per character.
+    pub fn parse(input: &str) -> Result<Puzzle, ParseError> {
+        let locations = Grid::parse(input, "'O', '.' or '#'", |c| match c {
+            'O' => Some(Occupance::Round),
+            '.' => Some(Occupance::Empty),
+            '#' => Some(Occupance::Cube),
+            _ => None,
+        })?;
+        Ok(Puzzle { locations })
+    }
+}
+
+/// A step of tilting the platform.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event<'a> {
+    /// The platform after tilting it towards `direction`.
+    Tilt {
+        /// Where the round rocks rolled.
+        direction: Direction,
+        /// The platform after they stopped.
+        platform: &'a Puzzle,
+    },
+    /// The platform after `cycles` spin cycles.
+    Spin {
+        /// How many spin cycles ran.
+        cycles: usize,
+        /// The platform after them.
+        platform: &'a Puzzle,
+    },
+}
+
+impl Display for Event<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let platform = match self {
+            Event::Tilt {
+                direction,
+                platform,
+            } => {
+                write!(f, "tilt {}", direction)?;
+                platform
+            }
+            Event::Spin { cycles, platform } => {
+                write!(f, "spin cycle {}", cycles)?;
+                platform
+            }
+        };
+        writeln!(f, ", north load {}", platform.north_load())?;
+        write!(f, "{}", platform.locations)
+    }
+}
+
+/// The solution of the day's puzzle.
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 14;
+
+    type Input<'a> = Puzzle;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        Ok(Puzzle::parse(input)?)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer> {
+        let mut puzzle = input.clone();
+        puzzle.north();
+        Ok(puzzle.north_load().into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer> {
+        Ok(spin_cycles(input.clone()).into())
+    }
+
+    fn run_traced(input: &Self::Input<'_>, part: Part, tracer: &mut Tracer) -> Result<Answer> {
+        let load = match part {
+            Part::One => {
+                let mut puzzle = input.clone();
+                puzzle.north();
+                tracer.observe(&Event::Tilt {
+                    direction: Direction::Up,
+                    platform: &puzzle,
+                });
+                puzzle.north_load()
+            }
+            Part::Two => spin_cycles_traced(input.clone(), tracer),
+        };
+        Ok(load.into())
+    }
+
+    fn render(input: &Self::Input<'_>, part: Part) -> Result<Option<Picture>> {
+        let platform = match part {
+            Part::One => {
+                let mut puzzle = input.clone();
+                puzzle.north();
+                puzzle
+            }
+            Part::Two => spun(input.clone(), &mut Ignore),
+        };
+        Ok(Some(render(&platform)))
+    }
+}
+
+/// The platform with its round and cube-shaped rocks highlighted.
+pub fn render(puzzle: &Puzzle) -> Picture {
+    let symbols = puzzle.locations.map(|occupance| occupance.symbol());
+    let rocks = |kind| {
+        puzzle
+            .locations
+            .enumerate()
+            .filter(move |&(_, &occupance)| occupance == kind)
+            .map(|(position, _)| position)
+    };
+    Picture::new(symbols)
+        .with_overlay("cube-shaped rocks", Color::Blue, rocks(Occupance::Cube))
+        .with_overlay("round rocks", Color::Yellow, rocks(Occupance::Round))
+}
+
+/// Runs a billion spin cycles and returns the resulting north load.
+pub fn spin_cycles(puzzle: Puzzle) -> usize {
+    spin_cycles_traced(puzzle, &mut Ignore)
+}
+
+/// Like [`spin_cycles`], reporting every tilt and spin cycle to `observer` until the platform
+/// repeats, after which the rest are skipped.
+pub fn spin_cycles_traced(
+    puzzle: Puzzle,
+    observer: &mut impl for<'a> Observer<Event<'a>>,
+) -> usize {
+    spun(puzzle, observer).north_load()
+}
+
+/// The platform after a billion spin cycles, reporting them like [`spin_cycles_traced`].
+fn spun(puzzle: Puzzle, observer: &mut impl for<'a> Observer<Event<'a>>) -> Puzzle {
+    let mut cycles = 0;
+    let spin = |puzzle: &Puzzle| {
+        let mut puzzle = puzzle.clone();
+        puzzle.spin_cycle_traced(observer);
+        cycles += 1;
+        observer.observe(&Event::Spin {
+            cycles,
+            platform: &puzzle,
+        });
+        puzzle
+    };
+    cycle::state_at(puzzle, spin, 1_000_000_000, Mode::Hashed)
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{input::fixture, solution::answer, Part};
+
+    use super::*;
+
+    fn example() -> String {
+        fixture(env!("CARGO_MANIFEST_DIR"), "example.txt")
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(
+            answer::<Day>(&example(), Part::One),
+            Ok(Answer::Integer(136))
+        );
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(
+            answer::<Day>(&example(), Part::Two),
+            Ok(Answer::Integer(64))
+        );
+    }
+
+    #[test]
+    fn spin_cycle() {
+        let input = example();
+        let mut puzzle = Day::parse(&input).unwrap();
+        puzzle.spin_cycle();
+        let expected = Day::parse(
+            ".....#....\n....#...O#\n...OO##...\n.OO#......\n.....OOO#.\n.O#...O#.#\n....O#....\n......OOOO\n#...O###..\n#..OO#....\n",
+        )
+        .unwrap();
+        assert_eq!(puzzle, expected);
+    }
+
+    #[test]
+    fn render_example() {
+        let puzzle = Day::parse(&example()).unwrap();
+        let mut tilted = puzzle.clone();
+        tilted.north();
+        let picture = Day::render(&puzzle, Part::One).unwrap().unwrap();
+        assert_eq!(picture.cells.to_string(), tilted.locations.to_string());
+        let cells = picture
+            .overlays
+            .iter()
+            .map(|overlay| (overlay.name.as_str(), overlay.cells.len()))
+            .collect::<Vec<_>>();
+        assert_eq!(cells, [("cube-shaped rocks", 17), ("round rocks", 18)]);
+        let picture = Day::render(&puzzle, Part::Two).unwrap().unwrap();
+        assert_eq!(picture.overlays[1].cells.len(), 18);
+    }
+
+    #[test]
+    fn rocks_at_the_edges() {
+        for (input, load) in [
+            ("O\n", 1),
+            ("#O\n", 1),
+            ("O#\n", 1),
+            ("O\n#\n", 2),
+            ("#\nO\n", 1),
+        ] {
+            assert_eq!(
+                answer::<Day>(input, Part::Two),
+                Ok(Answer::Integer(load)),
+                "{:?}",
+                input
+            );
+        }
+        let mut puzzle = Day::parse("O.\n.O\n").unwrap();
+        puzzle.south();
+        puzzle.east();
+        assert_eq!(puzzle.locations.to_string(), "..\nOO\n");
+    }
+}
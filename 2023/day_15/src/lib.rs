@@ -0,0 +1,178 @@
+//! 2023 day 15: Lens Library.
+
+#![warn(missing_docs)]
+
+use aoc_common::{
+    arith::{self, Policy},
+    parse, Answer, ParseError, Result, Solution,
+};
+
+/// One step of the initialization sequence.
+pub struct Instruction<'a> {
+    /// The whole step, as hashed in part 1.
+    pub step: &'a [u8],
+    /// The label of the lens, which picks its box.
+    pub label: &'a [u8],
+    /// What to do with the lens.
+    pub kind: InstructionKind,
+}
+
+/// What a step does with the lens labelled `label`: put one of a focal length in, or take it out.
+pub enum InstructionKind {
+    /// `=`, putting in a lens of this focal length.
+    Set(u32),
+    /// `-`, taking the lens out.
+    Clear,
+}
+
+impl<'a> Instruction<'a> {
+    /// Parses `step`, a slice of `input`.
+    pub fn parse(input: &str, step: &'a str) -> Result<Self, ParseError> {
+        if let Some(label) = step.strip_suffix('-') {
+            Ok(Self {
+                step: step.as_bytes(),
+                label: label.as_bytes(),
+                kind: InstructionKind::Clear,
+            })
+        } else {
+            let (label, value) = parse::split_once(input, step, "=")?;
+            let value = parse::number(input, value)?;
+            Ok(Self {
+                step: step.as_bytes(),
+                label: label.as_bytes(),
+                kind: InstructionKind::Set(value),
+            })
+        }
+    }
+}
+
+/// The HASH algorithm, turning a string into a box number from 0 to 255.
+pub fn hash(input: &[u8]) -> usize {
+    let mut result = 0;
+    for &byte in input {
+        result = (result + byte as usize) * 17 % 256;
+    }
+    result
+}
+
+type Box<'a> = Vec<(&'a [u8], u32)>;
+
+fn run<'a>(bos: &mut Box<'a>, instruction: &Instruction<'a>) {
+    let index = bos
+        .iter()
+        .enumerate()
+        .find(|(_, (label, _))| *label == instruction.label)
+        .map(|(index, _)| index);
+    match instruction.kind {
+        InstructionKind::Set(value) => {
+            if let Some(index) = index {
+                bos[index].1 = value;
+            } else {
+                bos.push((instruction.label, value));
+            }
+        }
+        InstructionKind::Clear => {
+            if let Some(index) = index {
+                bos.remove(index);
+            }
+        }
+    }
+}
+
+type Lenses<'a> = Vec<Box<'a>>;
+
+/// The solution of the day's puzzle.
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 15;
+
+    type Input<'a> = Vec<Instruction<'a>>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        Ok(input
+            .trim()
+            .split(',')
+            .map(|step| Instruction::parse(input, step))
+            .collect::<Result<_, _>>()?)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer> {
+        let hashes = input
+            .iter()
+            .map(|instruction| hash(instruction.step) as i128);
+        arith::policy()
+            .sum::<usize>(hashes, "sum of hashes")
+            .map(Answer::from)
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer> {
+        focusing_power(input, arith::policy()).map(Answer::from)
+    }
+}
+
+/// Runs `instructions` on 256 empty boxes and sums the focusing power of the lenses left in them.
+pub fn focusing_power(instructions: &[Instruction], policy: Policy) -> Result<i128> {
+    let mut lenses: Lenses = vec![Box::default(); 256];
+    for instruction in instructions {
+        let box_index = hash(instruction.label);
+        run(&mut lenses[box_index], instruction);
+    }
+
+    let mut powers = vec![];
+    for (box_index, bos) in lenses.into_iter().enumerate() {
+        for (lens_index, (_, value)) in bos.into_iter().enumerate() {
+            let factors = [box_index as i128 + 1, lens_index as i128 + 1, value.into()];
+            powers.push(policy.product::<usize>(factors, "focusing power")?);
+        }
+    }
+    policy.sum::<usize>(powers, "total focusing power")
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{input::fixture, solution::answer, Error, Part};
+
+    use super::*;
+
+    fn example() -> String {
+        fixture(env!("CARGO_MANIFEST_DIR"), "example.txt")
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(
+            answer::<Day>(&example(), Part::One),
+            Ok(Answer::Integer(1320))
+        );
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(
+            answer::<Day>(&example(), Part::Two),
+            Ok(Answer::Integer(145))
+        );
+    }
+
+    #[test]
+    fn hash_of_word() {
+        assert_eq!(hash(b"HASH"), 52);
+        assert_eq!(hash(b"rn=1"), 30);
+        assert_eq!(hash(b"rn"), 0);
+    }
+
+    #[test]
+    fn malformed_steps_are_rejected() {
+        for (input, column, expected) in [
+            ("rn=1,ab-cd,qp=3", 11, "\"=\""),
+            ("rn=1,qp=-3", 9, "number"),
+        ] {
+            let Err(Error::Parse(error)) = Day::parse(input) else {
+                panic!("{:?} parsed", input);
+            };
+            assert_eq!((error.column, error.expected.as_str()), (column, expected));
+        }
+    }
+}
@@ -0,0 +1,393 @@
+//! 2023 day 16: The Floor Will Be Lava.
+
+#![warn(missing_docs)]
+
+use std::{
+    collections::HashSet,
+    fmt::{Display, Formatter},
+};
+
+use aoc_common::{
+    geometry::Position,
+    parallel,
+    render::{Color, Picture},
+    trace::{Ignore, Observer, Tracer},
+    Answer, Direction, Grid, ParseError, Part, Result, Solution,
+};
+
+/// What is on a tile. A mirror is named after the corners it touches, so `/` touches the bottom
+/// left and `\` the bottom right.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OccupanceKind {
+    /// Empty space, `.`.
+    Empty,
+    /// A splitter, `-`.
+    HorizontalSplit,
+    /// A splitter, `|`.
+    VerticalSplit,
+    /// A mirror, `\`.
+    LeftBottomMirror,
+    /// A mirror, `/`.
+    RightBottomMirror,
+}
+
+impl OccupanceKind {
+    /// The character the tile is written as.
+    pub fn symbol(self) -> char {
+        match self {
+            OccupanceKind::Empty => '.',
+            OccupanceKind::HorizontalSplit => '-',
+            OccupanceKind::VerticalSplit => '|',
+            OccupanceKind::LeftBottomMirror => '\\',
+            OccupanceKind::RightBottomMirror => '/',
+        }
+    }
+}
+
+/// A tile, and whether a beam has passed it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Occupance {
+    /// What is on the tile.
+    pub kind: OccupanceKind,
+    /// Whether a beam has passed the tile.
+    pub energized: bool,
+}
+
+/// A beam on a tile, heading in `direction`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Light {
+    /// The tile the beam is on.
+    pub position: Position,
+    /// Where the beam is heading.
+    pub direction: Direction,
+}
+
+/// The contraption of mirrors and splitters.
+#[derive(Debug, Clone)]
+pub struct Puzzle {
+    /// Every tile of the contraption.
+    pub grid: Grid<Occupance>,
+}
+
+/// Moves `light` one block in `direction`, unless that leaves the grid.
+fn go(light: Light, direction: Direction, puzzle: &Puzzle) -> Option<Light> {
+    puzzle
+        .grid
+        .step(light.position, direction)
+        .map(|position| Light {
+            position,
+            direction,
+        })
+}
+
+/// The beams `light` turns into after passing through its tile.
+pub fn next_light(light: Light, puzzle: &Puzzle) -> Vec<Light> {
+    match (light.direction, puzzle.grid[light.position].kind) {
+        (Direction::Up, OccupanceKind::Empty | OccupanceKind::VerticalSplit) => {
+            go(light, Direction::Up, puzzle).into_iter().collect()
+        }
+        (Direction::Down, OccupanceKind::Empty | OccupanceKind::VerticalSplit) => {
+            go(light, Direction::Down, puzzle).into_iter().collect()
+        }
+        (Direction::Left, OccupanceKind::Empty | OccupanceKind::HorizontalSplit) => {
+            go(light, Direction::Left, puzzle).into_iter().collect()
+        }
+        (Direction::Right, OccupanceKind::Empty | OccupanceKind::HorizontalSplit) => {
+            go(light, Direction::Right, puzzle).into_iter().collect()
+        }
+        (Direction::Up | Direction::Down, OccupanceKind::HorizontalSplit) => [
+            go(light, Direction::Left, puzzle),
+            go(light, Direction::Right, puzzle),
+        ]
+        .into_iter()
+        .flatten()
+        .collect(),
+        (Direction::Left | Direction::Right, OccupanceKind::VerticalSplit) => [
+            go(light, Direction::Up, puzzle),
+            go(light, Direction::Down, puzzle),
+        ]
+        .into_iter()
+        .flatten()
+        .collect(),
+        (Direction::Up, OccupanceKind::LeftBottomMirror) => {
+            go(light, Direction::Left, puzzle).into_iter().collect()
+        }
+        (Direction::Up, OccupanceKind::RightBottomMirror) => {
+            go(light, Direction::Right, puzzle).into_iter().collect()
+        }
+        (Direction::Down, OccupanceKind::LeftBottomMirror) => {
+            go(light, Direction::Right, puzzle).into_iter().collect()
+        }
+        (Direction::Down, OccupanceKind::RightBottomMirror) => {
+            go(light, Direction::Left, puzzle).into_iter().collect()
+        }
+        (Direction::Left, OccupanceKind::LeftBottomMirror) => {
+            go(light, Direction::Up, puzzle).into_iter().collect()
+        }
+        (Direction::Left, OccupanceKind::RightBottomMirror) => {
+            go(light, Direction::Down, puzzle).into_iter().collect()
+        }
+        (Direction::Right, OccupanceKind::LeftBottomMirror) => {
+            go(light, Direction::Down, puzzle).into_iter().collect()
+        }
+        (Direction::Right, OccupanceKind::RightBottomMirror) => {
+            go(light, Direction::Up, puzzle).into_iter().collect()
+        }
+    }
+}
+
+/// A step of [`energize`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    /// A beam enters the contraption.
+    Enter(Light),
+    /// A beam reaches a tile no beam has reached heading the same way, leaving `energized` tiles
+    /// energized so far.
+    Beam {
+        /// The beam on the tile.
+        light: Light,
+        /// How many tiles are energized so far.
+        energized: usize,
+    },
+}
+
+impl Display for Event {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Event::Enter(light) => write!(f, "enter {:?} {}", light.position, light.direction),
+            Event::Beam { light, energized } => write!(
+                f,
+                "beam {:?} {} energized {}",
+                light.position, light.direction, energized
+            ),
+        }
+    }
+}
+
+/// The number of tiles energized by a beam entering at `light`.
+pub fn count_energized(puzzle: Puzzle, light: Light) -> usize {
+    count_energized_traced(puzzle, light, &mut Ignore)
+}
+
+/// Like [`count_energized`], reporting every step to `observer`.
+pub fn count_energized_traced(
+    puzzle: Puzzle,
+    light: Light,
+    observer: &mut impl Observer<Event>,
+) -> usize {
+    energize(puzzle, light, observer)
+        .grid
+        .iter()
+        .filter(|o| o.energized)
+        .count()
+}
+
+/// The puzzle with the tiles energized by a beam entering at `light` marked.
+pub fn energize(mut puzzle: Puzzle, light: Light, observer: &mut impl Observer<Event>) -> Puzzle {
+    observer.observe(&Event::Enter(light));
+    let mut lights = vec![light];
+    let mut history = HashSet::new();
+    let mut energized = 0;
+    while let Some(light) = lights.pop() {
+        if !history.contains(&light) {
+            history.insert(light);
+            let occupance = &mut puzzle.grid[light.position];
+            if !occupance.energized {
+                occupance.energized = true;
+                energized += 1;
+            }
+            observer.observe(&Event::Beam { light, energized });
+            lights.extend(next_light(light, &puzzle));
+        }
+    }
+    puzzle
+}
+
+/// The beam of part 1, entering the top left tile heading right.
+pub const FIRST_LIGHT: Light = Light {
+    position: (0, 0),
+    direction: Direction::Right,
+};
+
+/// The solution of the day's puzzle.
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 16;
+
+    type Input<'a> = Puzzle;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        let grid = Grid::parse(input, "'.', '|', '-', '/' or '\\'", |c| {
+            let kind = match c {
+                '.' => OccupanceKind::Empty,
+                '|' => OccupanceKind::VerticalSplit,
+                '-' => OccupanceKind::HorizontalSplit,
+                '/' => OccupanceKind::RightBottomMirror,
+                '\\' => OccupanceKind::LeftBottomMirror,
+                _ => return None,
+            };
+            Some(Occupance {
+                kind,
+                energized: false,
+            })
+        })?;
+        if grid.width() == 0 {
+            return Err(ParseError::at(input, input, "a row of tiles").into());
+        }
+        Ok(Puzzle { grid })
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer> {
+        Ok(count_energized(input.clone(), FIRST_LIGHT).into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer> {
+        Ok(max_energized(input).into())
+    }
+
+    fn run_traced(input: &Self::Input<'_>, part: Part, tracer: &mut Tracer) -> Result<Answer> {
+        let energized = match part {
+            Part::One => count_energized_traced(input.clone(), FIRST_LIGHT, tracer),
+            Part::Two => max_energized_traced(input, tracer),
+        };
+        Ok(energized.into())
+    }
+
+    fn render(input: &Self::Input<'_>, part: Part) -> Result<Option<Picture>> {
+        let light = match part {
+            Part::One => FIRST_LIGHT,
+            Part::Two => best_edge_light(input),
+        };
+        Ok(Some(render(input, light)))
+    }
+}
+
+/// The contraption with the tiles energized by a beam entering at `light` highlighted.
+pub fn render(puzzle: &Puzzle, light: Light) -> Picture {
+    let energized = energize(puzzle.clone(), light, &mut Ignore)
+        .grid
+        .enumerate()
+        .filter(|(_, occupance)| occupance.energized)
+        .map(|(position, _)| position)
+        .collect::<Vec<_>>();
+    Picture::new(puzzle.grid.map(|occupance| occupance.kind.symbol()))
+        .with_overlay("energized", Color::Yellow, energized)
+        .with_overlay("entry", Color::Red, [light.position])
+}
+
+/// The beams entering from every edge tile, heading away from that edge.
+pub fn edge_lights(puzzle: &Puzzle) -> Vec<Light> {
+    let (width, height) = (puzzle.grid.width(), puzzle.grid.height());
+    let light = |position, direction| Light {
+        position,
+        direction,
+    };
+    // Top row, bottom row, left column, right column
+    (0..width)
+        .map(|inner| light((0, inner), Direction::Down))
+        .chain((0..width).map(|inner| light((height - 1, inner), Direction::Up)))
+        .chain((0..height).map(|outer| light((outer, 0), Direction::Right)))
+        .chain((0..height).map(|outer| light((outer, width - 1), Direction::Left)))
+        .collect()
+}
+
+/// The most tiles energized by a beam entering from any edge tile.
+pub fn max_energized(puzzle: &Puzzle) -> usize {
+    edge_energized(puzzle).into_iter().max().unwrap_or(0)
+}
+
+/// How many tiles each of the [`edge_lights`] energizes, in the same order.
+fn edge_energized(puzzle: &Puzzle) -> Vec<usize> {
+    parallel::map(&edge_lights(puzzle), |&light| {
+        count_energized(puzzle.clone(), light)
+    })
+}
+
+/// Like [`max_energized`], reporting the steps of every beam in turn to `observer`, so always on
+/// one thread.
+pub fn max_energized_traced(puzzle: &Puzzle, observer: &mut impl Observer<Event>) -> usize {
+    edge_lights(puzzle)
+        .into_iter()
+        .map(|light| count_energized_traced(puzzle.clone(), light, observer))
+        .max()
+        .unwrap_or(0)
+}
+
+/// The edge beam that energizes the most tiles.
+pub fn best_edge_light(puzzle: &Puzzle) -> Light {
+    edge_lights(puzzle)
+        .into_iter()
+        .zip(edge_energized(puzzle))
+        .max_by_key(|&(_, energized)| energized)
+        .map_or(FIRST_LIGHT, |(light, _)| light)
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{input::fixture, solution::answer, Error, Part};
+
+    use super::*;
+
+    fn example() -> String {
+        fixture(env!("CARGO_MANIFEST_DIR"), "example.txt")
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(
+            answer::<Day>(&example(), Part::One),
+            Ok(Answer::Integer(46))
+        );
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(
+            answer::<Day>(&example(), Part::Two),
+            Ok(Answer::Integer(51))
+        );
+    }
+
+    #[test]
+    fn empty_contraption() {
+        for input in ["", "\n\n"] {
+            assert!(
+                matches!(Day::parse(input), Err(Error::Parse(_))),
+                "{:?}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn trace_ends_with_every_tile_energized() {
+        let puzzle = Day::parse(&example()).unwrap();
+        let mut events = vec![];
+        let count = count_energized_traced(puzzle.clone(), FIRST_LIGHT, &mut events);
+        assert_eq!(count, count_energized(puzzle.clone(), FIRST_LIGHT));
+        assert_eq!(events.first(), Some(&Event::Enter(FIRST_LIGHT)));
+        let Some(&Event::Beam { energized, .. }) = events.last() else {
+            panic!("the trace ends with {:?}", events.last());
+        };
+        assert_eq!(energized, count);
+
+        // The beams of the trace went through exactly the energized tiles.
+        let traced = events
+            .iter()
+            .filter_map(|event| match event {
+                Event::Beam { light, .. } => Some(light.position),
+                Event::Enter(_) => None,
+            })
+            .collect::<HashSet<_>>();
+        let expected = energize(puzzle, FIRST_LIGHT, &mut Ignore)
+            .grid
+            .enumerate()
+            .filter(|(_, occupance)| occupance.energized)
+            .map(|(position, _)| position)
+            .collect::<HashSet<_>>();
+        assert_eq!(traced, expected);
+        assert_eq!(traced.len(), 46);
+    }
+}
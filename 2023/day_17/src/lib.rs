@@ -0,0 +1,209 @@
+//! 2023 day 17: Clumsy Crucible.
+
+#![warn(missing_docs)]
+
+use aoc_common::{
+    geometry::Position,
+    render::{Color, Picture},
+    shortest_path::{self, Path},
+    Answer, Direction, Error, Grid, ParseError, Part, Result, Solution,
+};
+
+/// The heat loss of every city block.
+pub struct Puzzle {
+    /// The heat loss of the block at every position.
+    pub heat_losses: Grid<u32>,
+}
+
+/// How many blocks a crucible must move before turning or stopping, and may move before turning.
+#[derive(Debug, Clone, Copy)]
+pub struct Crucible {
+    /// How many blocks it must move in a line before turning or stopping.
+    pub min_steps_before_turn_or_stop: usize,
+    /// How many blocks it may move in a line before it must turn.
+    pub max_steps_before_turn: usize,
+}
+
+/// The crucible of part 1.
+pub const CRUCIBLE: Crucible = Crucible {
+    min_steps_before_turn_or_stop: 1,
+    max_steps_before_turn: 3,
+};
+
+/// The ultra crucible of part 2.
+pub const ULTRA_CRUCIBLE: Crucible = Crucible {
+    min_steps_before_turn_or_stop: 4,
+    max_steps_before_turn: 10,
+};
+
+/// Where a crucible is, which way it last moved and how many blocks it has moved that way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct State {
+    /// The block the crucible is on.
+    pub position: Position,
+    /// The way it last moved.
+    pub direction: Direction,
+    /// How many blocks it has moved that way.
+    pub steps: usize,
+}
+
+/// The states reachable in one move from `state`, with the heat loss of the block moved onto.
+fn moves<'a>(
+    puzzle: &'a Puzzle,
+    crucible: Crucible,
+    state: &State,
+) -> impl Iterator<Item = (State, u32)> + 'a {
+    let state = *state;
+    let straight = (state.steps < crucible.max_steps_before_turn).then_some(state.direction);
+    let turns = (state.steps >= crucible.min_steps_before_turn_or_stop)
+        .then_some([state.direction.turn_left(), state.direction.turn_right()]);
+    straight
+        .into_iter()
+        .chain(turns.into_iter().flatten())
+        .filter_map(move |direction| {
+            let position = puzzle.heat_losses.step(state.position, direction)?;
+            let steps = if direction == state.direction {
+                state.steps + 1
+            } else {
+                1
+            };
+            let next = State {
+                position,
+                direction,
+                steps,
+            };
+            Some((next, puzzle.heat_losses[position]))
+        })
+}
+
+/// The path with the least heat loss moving `crucible` from the top left block to the bottom right
+/// one, or `None` if it can't get there.
+pub fn find_path(puzzle: &Puzzle, crucible: Crucible) -> Option<Path<State, u32>> {
+    let heat_losses = &puzzle.heat_losses;
+    let goal = (heat_losses.height() - 1, heat_losses.width() - 1);
+    // Every block on the way costs at least the cheapest block.
+    let min_heat_loss = heat_losses.iter().copied().min().unwrap_or(0);
+    let starts = [Direction::Right, Direction::Down].map(|direction| State {
+        position: (0, 0),
+        direction,
+        steps: 0,
+    });
+    shortest_path::astar(
+        starts,
+        |state| moves(puzzle, crucible, state),
+        |state| {
+            let distance = goal.0 - state.position.0 + goal.1 - state.position.1;
+            distance as u32 * min_heat_loss
+        },
+        |state| state.position == goal && state.steps >= crucible.min_steps_before_turn_or_stop,
+    )
+}
+
+/// The least heat loss moving `crucible` from the top left block to the bottom right one.
+pub fn solve(puzzle: &Puzzle, crucible: Crucible) -> Result<u32> {
+    find_path(puzzle, crucible)
+        .map(|path| path.cost)
+        .ok_or_else(no_path)
+}
+
+/// The heat loss map with the path of least heat loss for `crucible` highlighted.
+pub fn render(puzzle: &Puzzle, crucible: Crucible) -> Result<Picture> {
+    let path = find_path(puzzle, crucible).ok_or_else(no_path)?;
+    let digits = puzzle
+        .heat_losses
+        .map(|&heat_loss| char::from_digit(heat_loss, 10).unwrap_or('?'));
+    let positions = path.states.iter().map(|state| state.position);
+    Ok(Picture::new(digits).with_overlay("path", Color::Red, positions))
+}
+
+fn no_path() -> Error {
+    Error::NoSolution("the crucible can't reach the bottom right".to_string())
+}
+
+/// The solution of the day's puzzle.
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 17;
+
+    type Input<'a> = Puzzle;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        let heat_losses = Grid::parse(input, "digit", |c| c.to_digit(10))?;
+        if heat_losses.width() == 0 {
+            return Err(ParseError::at(input, input, "a row of digits").into());
+        }
+        Ok(Puzzle { heat_losses })
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer> {
+        solve(input, CRUCIBLE).map(Answer::from)
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer> {
+        solve(input, ULTRA_CRUCIBLE).map(Answer::from)
+    }
+
+    fn render(input: &Self::Input<'_>, part: Part) -> Result<Option<Picture>> {
+        let crucible = match part {
+            Part::One => CRUCIBLE,
+            Part::Two => ULTRA_CRUCIBLE,
+        };
+        render(input, crucible).map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{input::fixture, solution::answer, Part};
+
+    use super::*;
+
+    fn example_1() -> String {
+        fixture(env!("CARGO_MANIFEST_DIR"), "example_1.txt")
+    }
+
+    fn example_2() -> String {
+        fixture(env!("CARGO_MANIFEST_DIR"), "example_2.txt")
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(
+            answer::<Day>(&example_1(), Part::One),
+            Ok(Answer::Integer(102))
+        );
+    }
+
+    #[test]
+    fn part2_example_1() {
+        assert_eq!(
+            answer::<Day>(&example_1(), Part::Two),
+            Ok(Answer::Integer(94))
+        );
+    }
+
+    #[test]
+    fn empty_city() {
+        for input in ["", "\n\n"] {
+            assert!(
+                matches!(Day::parse(input), Err(Error::Parse(_))),
+                "{:?}",
+                input
+            );
+        }
+        assert!(matches!(
+            answer::<Day>("1\n", Part::One),
+            Err(Error::NoSolution(_))
+        ));
+    }
+
+    #[test]
+    fn part2_example_2() {
+        assert_eq!(
+            answer::<Day>(&example_2(), Part::Two),
+            Ok(Answer::Integer(71))
+        );
+    }
+}
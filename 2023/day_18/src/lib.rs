@@ -0,0 +1,659 @@
+//! 2023 day 18: Lavaduct Lagoon.
+
+#![warn(missing_docs)]
+
+use aoc_common::{
+    geometry::{Point, Position},
+    parse,
+    random::Rng,
+    render::{Color, Picture},
+    Answer, Direction, Grid, Interval, ParseError, Part, Result, Solution,
+};
+
+pub mod reference;
+
+/// A direction to dig in and the distance to dig.
+pub type Instruction = (Direction, i64);
+
+/// The dig plan, read both as written and as decoded from the colors.
+pub struct DigPlan {
+    /// The instructions as written, for part 1.
+    pub instructions: Vec<Instruction>,
+    /// The instructions decoded from the colors, for part 2.
+    pub color_instructions: Vec<Instruction>,
+}
+
+impl DigPlan {
+    /// Parses lines like "R 6 (#70c710)". Both plans must dig a trench that turns at every
+    /// instruction, ends where it started and never touches itself elsewhere.
+    pub fn parse(input: &str) -> Result<DigPlan, ParseError> {
+        let mut instructions = vec![];
+        let mut color_instructions = vec![];
+        let mut lines = vec![];
+        let mut codes = vec![];
+        for line in input.lines() {
+            let mut parts = line.split_whitespace();
+            let token = parse::next(input, line, &mut parts, "direction")?;
+            let direction = match token {
+                "R" => Direction::Right,
+                "D" => Direction::Down,
+                "L" => Direction::Left,
+                "U" => Direction::Up,
+                _ => return Err(ParseError::at(input, token, "'R', 'D', 'L' or 'U'")),
+            };
+            let token = parse::next(input, line, &mut parts, "distance")?;
+            let distance = parse::number(input, token)?;
+            if distance < 1 {
+                return Err(ParseError::at(input, token, "a positive distance"));
+            }
+            instructions.push((direction, distance));
+            lines.push(line);
+
+            let color = parse::next(input, line, &mut parts, "color")?;
+            let code = parse::strip_prefix(input, color, "(#")?;
+            let code = code
+                .strip_suffix(')')
+                .filter(|code| code.len() == 6 && code.is_char_boundary(5))
+                .ok_or_else(|| ParseError::at(input, code, "6 hex digits and ')'"))?;
+            let distance = i64::from_str_radix(&code[..5], 16)
+                .ok()
+                .filter(|&distance| distance > 0)
+                .ok_or_else(|| ParseError::at(input, code, "positive hex distance"))?;
+            let direction = match code.as_bytes()[5] {
+                b'0' => Direction::Right,
+                b'1' => Direction::Down,
+                b'2' => Direction::Left,
+                b'3' => Direction::Up,
+                _ => return Err(ParseError::at(input, &code[5..], "'0', '1', '2' or '3'")),
+            };
+            color_instructions.push((direction, distance));
+            codes.push(code);
+        }
+        check_trench(input, &instructions, &lines)?;
+        check_trench(input, &color_instructions, &codes)?;
+        Ok(DigPlan {
+            instructions,
+            color_instructions,
+        })
+    }
+}
+
+/// How far from the start, in meters, a trench may go, so that the area of a lagoon fits an `i64`.
+const MAX_EXTENT: i64 = 1 << 30;
+
+/// Checks that `instructions` dig a trench that turns at every instruction, stays within
+/// [`MAX_EXTENT`], ends where it started and touches itself only where one instruction follows
+/// another. Errors are located at the token of the same index in `tokens`.
+fn check_trench(
+    input: &str,
+    instructions: &[Instruction],
+    tokens: &[&str],
+) -> Result<(), ParseError> {
+    let Some(&(mut previous, _)) = instructions.last() else {
+        return Err(ParseError::at(input, input, "a dig plan"));
+    };
+    let mut edges: Vec<(Point, Point)> = vec![];
+    let mut position = (0, 0);
+    for (&(direction, distance), &token) in instructions.iter().zip(tokens) {
+        if direction == previous || direction == previous.opposite() {
+            return Err(ParseError::at(input, token, "a turn"));
+        }
+        // Any longer distance is too long, and might overflow.
+        let next = direction.step(position, distance.min(2 * MAX_EXTENT + 1));
+        if next.0.abs() > MAX_EXTENT || next.1.abs() > MAX_EXTENT {
+            return Err(ParseError::at(input, token, "a trench within 2^30 meters"));
+        }
+        edges.push((position, next));
+        (position, previous) = (next, direction);
+    }
+    if position != (0, 0) {
+        let expected = "a trench ending where it started";
+        return Err(ParseError::at(input, tokens[tokens.len() - 1], expected));
+    }
+
+    let bounds = |(from, to): (Point, Point)| {
+        let rows = Interval::inclusive(from.0.min(to.0), from.0.max(to.0));
+        let cols = Interval::inclusive(from.1.min(to.1), from.1.max(to.1));
+        (rows, cols)
+    };
+    for index in 0..edges.len() {
+        let (rows, cols) = bounds(edges[index]);
+        // The edges just before and after share a corner with this one, and the last edge comes
+        // just before the first.
+        let end = edges.len() - usize::from(index == 0);
+        for other in index + 2..end {
+            let (other_rows, other_cols) = bounds(edges[other]);
+            let rows_overlap = rows.intersection(&other_rows).is_some();
+            if rows_overlap && cols.intersection(&other_cols).is_some() {
+                let expected = "a trench not touching itself";
+                return Err(ParseError::at(input, tokens[other], expected));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Corner {
+    position: Point,
+    direction: Direction,
+    up: bool,
+}
+
+#[derive(Debug, Clone)]
+struct Puzzle {
+    corners: Vec<Corner>,
+}
+
+#[derive(Debug, Clone)]
+struct Block {
+    height: i64,
+    segments: Vec<Segment>,
+}
+
+impl Block {
+    fn area(&self, inner_is_up: bool) -> i64 {
+        self.height * length(&self.segments, inner_is_up)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Segment {
+    first: i64,
+    last: i64,
+    last_up: bool,
+}
+
+fn length(segments: &[Segment], inner_is_up: bool) -> i64 {
+    let mut result = 0;
+    let mut inner = false;
+    for index in 0..segments.len() {
+        let segment = segments[index];
+        if inner {
+            result += segment.first - segments[index - 1].last - 1;
+        }
+        result += segment.last - segment.first + 1;
+        inner = segment.last_up == inner_is_up;
+    }
+    assert!(!inner);
+    result
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Wall {
+    above_corner_index: usize,
+    is_corner: bool,
+}
+
+impl Puzzle {
+    fn new(instructions: &[Instruction]) -> Puzzle {
+        let mut position = (0, 0);
+        let mut corners: Vec<Corner> = vec![];
+        for &(direction, distance) in instructions {
+            if let Some(corner) = corners.last() {
+                assert!(corner.direction != direction && corner.direction.opposite() != direction);
+            }
+            let up = match direction {
+                Direction::Up => true,
+                Direction::Down => false,
+                _ => corners.last().map(|corner| corner.up).unwrap_or(true), // First corner `up` may be decided by last corner.
+            };
+            corners.push(Corner {
+                position,
+                direction,
+                up,
+            });
+            position = direction.step(position, distance);
+        }
+
+        match corners.last().unwrap().direction {
+            Direction::Up => corners.first_mut().unwrap().up = true,
+            Direction::Down => corners.first_mut().unwrap().up = false,
+            _ => {}
+        }
+
+        Puzzle { corners }
+    }
+
+    fn to_blocks(&self) -> (Vec<Block>, bool) {
+        // Sort the corner indices by position.
+        let mut corners = self.corners.iter().enumerate().collect::<Vec<_>>();
+        corners.sort_by_key(|corner| corner.1.position);
+
+        // Group the corners that have same vertical position.
+        let mut corner_indices = vec![];
+        let mut index = 0;
+        while index < corners.len() {
+            let mut group = vec![];
+            group.push(corners[index].0);
+            index += 1;
+            while index < corners.len()
+                && corners[index].1.position.0 == corners[index - 1].1.position.0
+            {
+                group.push(corners[index].0);
+                index += 1;
+            }
+            corner_indices.push(group);
+        }
+        for corner_indices in &corner_indices {
+            assert!(corner_indices.iter().all(|&index| {
+                let corner = self.corners[index];
+                corner.position.0 == self.corners[corner_indices[0]].position.0
+            }));
+        }
+
+        let mut walls = corner_indices[0]
+            .iter()
+            .map(|&index| Wall {
+                above_corner_index: index,
+                is_corner: true,
+            })
+            .collect::<Vec<_>>();
+        let mut blocks = vec![Block {
+            height: 1,
+            segments: self.create_segments(&walls),
+        }];
+        let mut corner_indices_index = 1;
+        while corner_indices_index < corner_indices.len() {
+            // From now on, we only consider the walls extending down, including top left and top right corners, and edges.
+            // The extending walls are all not corners.
+            let walls_extending_down = walls
+                .iter()
+                .copied()
+                .filter_map(|wall| {
+                    if self.is_wall_extending_down(wall) {
+                        Some(Wall {
+                            above_corner_index: wall.above_corner_index,
+                            is_corner: false,
+                        })
+                    } else {
+                        None
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            // These walls form a block.
+            let last_height = self.corners[corner_indices[corner_indices_index - 1][0]]
+                .position
+                .0;
+            let current_height = self.corners[corner_indices[corner_indices_index][0]]
+                .position
+                .0;
+            let height = current_height - last_height - 1;
+            if height > 0 {
+                blocks.push(Block {
+                    height,
+                    segments: self.create_segments(&walls_extending_down),
+                });
+            }
+
+            // New walls include all the new corners.
+            let mut new_walls = corner_indices[corner_indices_index]
+                .iter()
+                .map(|&index| Wall {
+                    above_corner_index: index,
+                    is_corner: true,
+                })
+                .collect::<Vec<_>>();
+            // And the old walls that are not connected to any of the new corners.
+            for wall in walls_extending_down {
+                if !corner_indices[corner_indices_index]
+                    .iter()
+                    .any(|&index| self.is_wall_connected_to_corner(wall, index))
+                {
+                    new_walls.push(Wall {
+                        above_corner_index: wall.above_corner_index,
+                        is_corner: false,
+                    })
+                }
+            }
+            // Sort walls by horizontal position.
+            new_walls.sort_by_key(|wall| self.corners[wall.above_corner_index].position.1);
+
+            blocks.push(Block {
+                height: 1,
+                segments: self.create_segments(&new_walls),
+            });
+            walls = new_walls;
+            corner_indices_index += 1;
+        }
+
+        let inner_is_up = self.corners[corner_indices[0][0]].up;
+
+        (blocks, inner_is_up)
+    }
+
+    fn is_wall_extending_down(&self, wall: Wall) -> bool {
+        if !wall.is_corner {
+            return true;
+        }
+
+        let from_index = (wall.above_corner_index + self.corners.len() - 1) % self.corners.len();
+        let from_direction = self.corners[from_index].direction;
+        let to_direction = self.corners[wall.above_corner_index].direction;
+        match (from_direction, to_direction) {
+            (Direction::Up, Direction::Left) => true,
+            (Direction::Up, Direction::Right) => true,
+            (Direction::Down, Direction::Left) => false,
+            (Direction::Down, Direction::Right) => false,
+            (Direction::Left, Direction::Up) => false,
+            (Direction::Left, Direction::Down) => true,
+            (Direction::Right, Direction::Up) => false,
+            (Direction::Right, Direction::Down) => true,
+            _ => unreachable!(),
+        }
+    }
+
+    fn is_wall_connected_to_corner(&self, wall: Wall, corner_index: usize) -> bool {
+        let from_index = (wall.above_corner_index + self.corners.len() - 1) % self.corners.len();
+        let to_index = (wall.above_corner_index + 1) % self.corners.len();
+        from_index == corner_index || to_index == corner_index
+    }
+
+    fn create_segments(&self, walls: &[Wall]) -> Vec<Segment> {
+        let mut result = vec![];
+        let mut index = 0;
+        while index < walls.len() {
+            if index + 1 < walls.len() {
+                if let Some(segment) = self.create_segment_opt(walls[index], walls[index + 1]) {
+                    result.push(segment);
+                    index += 2;
+                    continue;
+                }
+            }
+
+            let corner = self.corners[walls[index].above_corner_index];
+            result.push(Segment {
+                first: corner.position.1,
+                last: corner.position.1,
+                last_up: corner.up,
+            });
+            index += 1;
+        }
+        result
+    }
+
+    fn create_segment_opt(&self, wall1: Wall, wall2: Wall) -> Option<Segment> {
+        if !(wall1.is_corner && wall2.is_corner) {
+            return None;
+        }
+        if (wall1.above_corner_index + 1) % self.corners.len() == wall2.above_corner_index
+            || (wall2.above_corner_index + 1) % self.corners.len() == wall1.above_corner_index
+        {
+            let corner1 = self.corners[wall1.above_corner_index];
+            let corner2 = self.corners[wall2.above_corner_index];
+            let (first, last, last_up) = if corner1.position.1 < corner2.position.1 {
+                (corner1.position.1, corner2.position.1, corner2.up)
+            } else {
+                (corner2.position.1, corner1.position.1, corner1.up)
+            };
+            Some(Segment {
+                first,
+                last,
+                last_up,
+            })
+        } else {
+            None
+        }
+    }
+}
+
+/// The area of the lagoon dug by `instructions`, trench included.
+pub fn lagoon_area(instructions: &[Instruction]) -> i64 {
+    let puzzle = Puzzle::new(instructions);
+    let (blocks, inner_is_up) = puzzle.to_blocks();
+    blocks
+        .iter()
+        .map(|block| block.area(inner_is_up))
+        .sum::<i64>()
+}
+
+/// The largest extent, in meters, of a lagoon rendered with one cell per meter. Larger ones only
+/// get cells where the trench turns, and for the spans between.
+const MAX_RENDER_EXTENT: i64 = 1000;
+
+/// The coordinates where cells start along one axis, and where the last one ends. Every coordinate
+/// in `coordinates` starts a cell of its own, and there is an empty cell on either side.
+fn cell_bounds(coordinates: &[i64]) -> Vec<i64> {
+    let min = coordinates.iter().copied().min().unwrap_or(0);
+    let max = coordinates.iter().copied().max().unwrap_or(0);
+    if max - min < MAX_RENDER_EXTENT {
+        return (min - 1..=max + 2).collect();
+    }
+    let mut bounds = coordinates
+        .iter()
+        .flat_map(|&coordinate| [coordinate, coordinate + 1])
+        .chain([min - 1, max + 2])
+        .collect::<Vec<_>>();
+    bounds.sort();
+    bounds.dedup();
+    bounds
+}
+
+/// The lagoon dug by `instructions`, with the trench and the dug out interior highlighted.
+pub fn render(instructions: &[Instruction]) -> Picture {
+    let mut corners = vec![(0, 0)];
+    for &(direction, distance) in instructions {
+        let &last = corners.last().unwrap();
+        corners.push(direction.step(last, distance));
+    }
+    let rows = cell_bounds(&corners.iter().map(|corner| corner.0).collect::<Vec<_>>());
+    let cols = cell_bounds(&corners.iter().map(|corner| corner.1).collect::<Vec<_>>());
+    let cell = |(row, col): Point| -> Position {
+        (
+            rows.binary_search(&row).unwrap(),
+            cols.binary_search(&col).unwrap(),
+        )
+    };
+
+    let mut trench = Grid::filled(cols.len() - 1, rows.len() - 1, false);
+    for edge in corners.windows(2) {
+        let (from, to) = (cell(edge[0]), cell(edge[1]));
+        for row in from.0.min(to.0)..=from.0.max(to.0) {
+            for col in from.1.min(to.1)..=from.1.max(to.1) {
+                trench[(row, col)] = true;
+            }
+        }
+    }
+
+    // Flood the outside from the top left cell, which is never on the trench.
+    let mut outside = trench.map(|_| false);
+    let mut stack = vec![(0, 0)];
+    while let Some(position) = stack.pop() {
+        if trench[position] || outside[position] {
+            continue;
+        }
+        outside[position] = true;
+        stack.extend(trench.neighbors4(position));
+    }
+
+    let on_trench = trench
+        .enumerate()
+        .filter(|&(_, &trench)| trench)
+        .map(|(position, _)| position);
+    let interior = outside
+        .enumerate()
+        .filter(|&(position, &outside)| !outside && !trench[position])
+        .map(|(position, _)| position);
+    Picture::new(trench.map(|&trench| if trench { '#' } else { '.' }))
+        .with_overlay("trench", Color::Red, on_trench)
+        .with_overlay("interior", Color::Blue, interior)
+}
+
+/// Generates a dig plan around `size` side by side columns, each spanning random rows that share
+/// at least one with the column before. Distances are all even, so the trench never runs right
+/// next to itself, and the colors encode the same plan.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1) as u64;
+    // The left, right, top and bottom of every column.
+    let mut columns: Vec<(i64, i64, i64, i64)> = vec![];
+    let (mut left, mut previous) = (0, (0, size));
+    for _ in 0..size {
+        let shared = rng.range(previous.0..previous.1);
+        let (top, bottom) = (rng.range(0..shared + 1), rng.range(shared + 1..size + 1));
+        let right = left + rng.range(1..4) as i64;
+        columns.push((left, right, top as i64, bottom as i64));
+        left = right;
+        previous = (top, bottom);
+    }
+
+    let mut corners = vec![];
+    for &(left, right, top, _) in &columns {
+        corners.extend([(top, left), (top, right)]);
+    }
+    for &(left, right, _, bottom) in columns.iter().rev() {
+        corners.extend([(bottom, right), (bottom, left)]);
+    }
+    if rng.chance(1, 2) {
+        corners.reverse();
+    }
+
+    let mut instructions: Vec<Instruction> = vec![];
+    for (index, &from) in corners.iter().enumerate() {
+        let to: Point = corners[(index + 1) % corners.len()];
+        let (direction, distance) = match (to.0 - from.0, to.1 - from.1) {
+            (0, 0) => continue,
+            (0, distance) if distance > 0 => (Direction::Right, distance),
+            (0, distance) => (Direction::Left, -distance),
+            (distance, _) if distance > 0 => (Direction::Down, distance),
+            (distance, _) => (Direction::Up, -distance),
+        };
+        match instructions.last_mut() {
+            Some(last) if last.0 == direction => last.1 += distance * 2,
+            _ => instructions.push((direction, distance * 2)),
+        }
+    }
+
+    let mut result = String::new();
+    for (direction, distance) in instructions {
+        let (letter, digit) = match direction {
+            Direction::Right => ('R', 0),
+            Direction::Down => ('D', 1),
+            Direction::Left => ('L', 2),
+            Direction::Up => ('U', 3),
+        };
+        result.push_str(&format!(
+            "{} {} (#{:05x}{})\n",
+            letter, distance, distance, digit
+        ));
+    }
+    result
+}
+
+/// The solution of the day's puzzle.
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 18;
+
+    type Input<'a> = DigPlan;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        Ok(DigPlan::parse(input)?)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer> {
+        Ok(lagoon_area(&input.instructions).into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer> {
+        Ok(lagoon_area(&input.color_instructions).into())
+    }
+
+    fn render(input: &Self::Input<'_>, part: Part) -> Result<Option<Picture>> {
+        let instructions = match part {
+            Part::One => &input.instructions,
+            Part::Two => &input.color_instructions,
+        };
+        Ok(Some(render(instructions)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{input::fixture, random, solution::answer, Part};
+
+    use super::*;
+
+    fn example() -> String {
+        fixture(env!("CARGO_MANIFEST_DIR"), "example.txt")
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(
+            answer::<Day>(&example(), Part::One),
+            Ok(Answer::Integer(62))
+        );
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(
+            answer::<Day>(&example(), Part::Two),
+            Ok(Answer::Integer(952408144115))
+        );
+    }
+
+    #[test]
+    fn render_example() {
+        let plan = DigPlan::parse(&example()).unwrap();
+        let picture = render(&plan.instructions);
+        let dug = picture
+            .overlays
+            .iter()
+            .map(|overlay| overlay.cells.len())
+            .sum::<usize>();
+        assert_eq!(dug, 62);
+    }
+
+    #[test]
+    fn invalid_plans() {
+        let error = |input: &str| {
+            let Err(error) = DigPlan::parse(input) else {
+                panic!("{:?} parsed", input);
+            };
+            (error.line, error.column, error.expected)
+        };
+        assert_eq!(error(""), (1, 1, "a dig plan".to_string()));
+        assert_eq!(
+            error("R 2 (#000020)\nL 2 (#000022)\n"),
+            (1, 1, "a turn".to_string())
+        );
+        assert_eq!(
+            error("R 0 (#000000)\n"),
+            (1, 3, "a positive distance".to_string())
+        );
+        assert_eq!(
+            error("R 2 (#000020)\nD 2 (#000021)\nL 2 (#000022)\nU 1 (#000023)\n"),
+            (4, 1, "a trench ending where it started".to_string())
+        );
+        let crossing = "R 4 (#000040)\nD 2 (#000021)\nL 2 (#000022)\n\
+                        U 4 (#000043)\nL 2 (#000022)\nD 2 (#000021)\n";
+        assert_eq!(
+            error(crossing),
+            (4, 1, "a trench not touching itself".to_string())
+        );
+        assert_eq!(
+            error("R 2 (#000020)\nD 2 (#000021)\nL 2 (#000022)\nU 2 (#000013)\n"),
+            (4, 7, "a trench ending where it started".to_string())
+        );
+        assert_eq!(
+            error("R 2000000000 (#000020)\nD 2 (#000021)\n"),
+            (1, 1, "a trench within 2^30 meters".to_string())
+        );
+    }
+
+    #[test]
+    fn agrees_with_reference() {
+        let plan = DigPlan::parse(&example()).unwrap();
+        assert_eq!(reference::lagoon_area(&plan.instructions), 62);
+        random::assert_agree(
+            100,
+            |rng| generate(rng, 8),
+            |input| lagoon_area(&DigPlan::parse(input).unwrap().instructions),
+            |input| reference::lagoon_area(&DigPlan::parse(input).unwrap().instructions),
+        );
+    }
+}
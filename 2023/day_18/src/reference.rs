@@ -0,0 +1,40 @@
+//! Slow but simple solutions to check the fast ones against.
+
+use aoc_common::Grid;
+
+use super::Instruction;
+
+/// The lagoon area found by digging the trench one meter at a time and flooding the ground around
+/// it. Only for small plans.
+pub fn lagoon_area(instructions: &[Instruction]) -> i64 {
+    let mut position = (0, 0);
+    let mut dug = vec![position];
+    for &(direction, distance) in instructions {
+        for _ in 0..distance {
+            position = direction.step(position, 1);
+            dug.push(position);
+        }
+    }
+
+    // One meter of ground on every side, so that the flood can get all around the trench.
+    let top = dug.iter().map(|point| point.0).min().unwrap() - 1;
+    let left = dug.iter().map(|point| point.1).min().unwrap() - 1;
+    let bottom = dug.iter().map(|point| point.0).max().unwrap() + 1;
+    let right = dug.iter().map(|point| point.1).max().unwrap() + 1;
+    let (width, height) = ((right - left + 1) as usize, (bottom - top + 1) as usize);
+    let mut trench = Grid::filled(width, height, false);
+    for (row, col) in dug {
+        trench[((row - top) as usize, (col - left) as usize)] = true;
+    }
+
+    let mut outside = trench.map(|_| false);
+    let mut stack = vec![(0, 0)];
+    while let Some(position) = stack.pop() {
+        if trench[position] || outside[position] {
+            continue;
+        }
+        outside[position] = true;
+        stack.extend(trench.neighbors4(position));
+    }
+    outside.iter().filter(|&&outside| !outside).count() as i64
+}
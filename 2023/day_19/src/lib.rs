@@ -0,0 +1,733 @@
+//! 2023 day 19: Aplenty.
+
+#![warn(missing_docs)]
+
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter},
+};
+
+use aoc_common::{
+    arith::{self, Policy},
+    interval::Cuboid,
+    parse,
+    random::Rng,
+    trace::{Ignore, Observer, Tracer},
+    Answer, Interval, ParseError, Result, Solution,
+};
+
+pub mod reference;
+
+/// How a rule compares a rating to its value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compare {
+    /// `>`.
+    GreaterThan,
+    /// `<`.
+    LessThan,
+}
+
+/// Where a part goes next.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Decision<'a> {
+    /// `A`, the part is accepted.
+    Accept,
+    /// `R`, the part is rejected.
+    Reject,
+    /// The part goes on to the workflow with this name.
+    Workflow(&'a str),
+}
+
+impl<'a> Decision<'a> {
+    /// Reads `A`, `R` or the name of a workflow.
+    pub fn parse(decision: &'a str) -> Self {
+        match decision {
+            "A" => Decision::Accept,
+            "R" => Decision::Reject,
+            workflow => Decision::Workflow(workflow),
+        }
+    }
+}
+
+/// Sends a part to `decision` if its `key` rating compares to `value`.
+#[derive(Debug, Clone, Copy)]
+pub struct Rule<'a> {
+    /// The category of the rating compared.
+    pub key: u8,
+    /// How the rating is compared.
+    pub compare: Compare,
+    /// What the rating is compared to.
+    pub value: u32,
+    /// Where the part goes if the comparison holds.
+    pub decision: Decision<'a>,
+}
+
+/// The ratings of a part, keyed by category.
+pub type Part = HashMap<u8, u32>;
+
+/// All parts whose ratings lie in the given ranges, with one axis per category in `xmas` order.
+pub type PartInterval = Cuboid<u32, 4>;
+
+/// The categories, in the order of the axes of a [`PartInterval`].
+pub const CATEGORIES: [u8; 4] = *b"xmas";
+
+/// The axis of a [`PartInterval`] holding category `key`.
+pub fn axis(key: u8) -> usize {
+    CATEGORIES
+        .iter()
+        .position(|&category| category == key)
+        .expect("rule keys are parsed from \"xmas\"")
+}
+
+/// The parts with every rating from 1 to 4000.
+pub fn all_parts() -> PartInterval {
+    Cuboid::new([Interval::new(1, 4001); 4])
+}
+
+impl<'a> Rule<'a> {
+    /// The decision for `part`, or `None` if the rule doesn't apply to it.
+    pub fn process(&self, part: &Part) -> Option<Decision<'a>> {
+        let part_value = part[&self.key];
+        match self.compare {
+            Compare::GreaterThan => {
+                if part_value > self.value {
+                    Some(self.decision)
+                } else {
+                    None
+                }
+            }
+            Compare::LessThan => {
+                if part_value < self.value {
+                    Some(self.decision)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    /// Splits `part` into the parts the rule decides, with its decision, and the parts it doesn't.
+    pub fn process_interval(
+        &self,
+        part: PartInterval,
+    ) -> (Option<(PartInterval, Decision<'a>)>, Option<PartInterval>) {
+        let axis = axis(self.key);
+        let (decided, undecided) = match self.compare {
+            Compare::GreaterThan => {
+                // Saturating is exact, as no rating reaches `u32::MAX`.
+                let (below, above) = part.split_at(axis, self.value.saturating_add(1));
+                (above, below)
+            }
+            Compare::LessThan => part.split_at(axis, self.value),
+        };
+        (decided.map(|decided| (decided, self.decision)), undecided)
+    }
+
+    /// Parses something like "a<2006:qkq".
+    pub fn parse(input: &str, rule: &'a str) -> Result<Self, ParseError> {
+        let (condition, decision) = parse::split_once(input, rule, ":")?;
+        let key = parse_key(input, condition)?;
+        let compare = match condition.as_bytes().get(1) {
+            Some(b'>') => Compare::GreaterThan,
+            Some(b'<') => Compare::LessThan,
+            _ => return Err(ParseError::at(input, &condition[1..], "'<' or '>'")),
+        };
+        let value = parse::number(input, &condition[2..])?;
+        let decision = Decision::parse(decision);
+        Ok(Self {
+            key,
+            compare,
+            value,
+            decision,
+        })
+    }
+}
+
+/// Rules tried in order, and the decision when none applies.
+#[derive(Debug, Clone)]
+pub struct Workflow<'a> {
+    /// The rules, tried in order.
+    pub rules: Vec<Rule<'a>>,
+    /// Where the part goes when no rule applies.
+    pub fallback_decision: Decision<'a>,
+}
+
+impl<'a> Workflow<'a> {
+    /// Where `part` goes from this workflow.
+    pub fn process(&self, part: &Part) -> Decision<'a> {
+        for rule in &self.rules {
+            if let Some(decision) = rule.process(part) {
+                return decision;
+            }
+        }
+        self.fallback_decision
+    }
+
+    /// Splits `part` by the decision the workflow makes for each piece.
+    pub fn process_interval(&self, part: PartInterval) -> Vec<(PartInterval, Decision<'a>)> {
+        let mut result = vec![];
+        let mut maybe_undecided = Some(part);
+        for rule in &self.rules {
+            let Some(undecided) = maybe_undecided else {
+                break;
+            };
+            let (maybe_rule_decided, maybe_rule_undecided) = rule.process_interval(undecided);
+            if let Some(decided) = maybe_rule_decided {
+                result.push(decided);
+            }
+            maybe_undecided = maybe_rule_undecided;
+        }
+        if let Some(undecided) = maybe_undecided {
+            result.push((undecided, self.fallback_decision));
+        }
+        result
+    }
+
+    /// The names of the workflows it can send a part to, in order.
+    pub fn next_workflows(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.rules
+            .iter()
+            .map(|rule| rule.decision)
+            .chain([self.fallback_decision])
+            .filter_map(|decision| match decision {
+                Decision::Workflow(name) => Some(name),
+                _ => None,
+            })
+    }
+
+    /// Parses something like "a<2006:qkq,m>2090:A,rfg".
+    pub fn parse(input: &str, workflow: &'a str) -> Result<Self, ParseError> {
+        let (rules, fallback_decision) = workflow.rsplit_once(',').unwrap_or(("", workflow));
+        let rules = if rules.is_empty() {
+            vec![]
+        } else {
+            parse::separated(rules, ",", |rule| Rule::parse(input, rule))?
+        };
+        let fallback_decision = Decision::parse(fallback_decision);
+        Ok(Self {
+            rules,
+            fallback_decision,
+        })
+    }
+}
+
+/// A step of sending a part through the workflows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event<'a> {
+    /// A part starts through the workflows.
+    Part(&'a Part),
+    /// The part enters a workflow.
+    Workflow(&'a str),
+    /// The part is accepted.
+    Accept,
+    /// The part is rejected.
+    Reject,
+}
+
+impl Display for Event<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Event::Part(part) => {
+                let ratings = CATEGORIES
+                    .iter()
+                    .filter_map(|key| Some(format!("{}={}", *key as char, part.get(key)?)))
+                    .collect::<Vec<_>>();
+                write!(f, "part {{{}}}", ratings.join(","))
+            }
+            Event::Workflow(name) => write!(f, "workflow {}", name),
+            Event::Accept => write!(f, "accept"),
+            Event::Reject => write!(f, "reject"),
+        }
+    }
+}
+
+/// The workflows by name, starting at `in`.
+#[derive(Debug, Clone)]
+pub struct System<'a> {
+    /// Every workflow, by name.
+    pub workflows: HashMap<&'a str, Workflow<'a>>,
+}
+
+impl<'a> System<'a> {
+    /// Returns if accepted
+    pub fn process(&self, part: &Part) -> bool {
+        self.process_traced(part, &mut Ignore)
+    }
+
+    /// Like [`System::process`], reporting every workflow the part goes through to `observer`.
+    pub fn process_traced(
+        &self,
+        part: &Part,
+        observer: &mut impl for<'e> Observer<Event<'e>>,
+    ) -> bool {
+        observer.observe(&Event::Part(part));
+        let mut name = "in";
+        loop {
+            observer.observe(&Event::Workflow(name));
+            match self.workflows[name].process(part) {
+                Decision::Accept => {
+                    observer.observe(&Event::Accept);
+                    return true;
+                }
+                Decision::Reject => {
+                    observer.observe(&Event::Reject);
+                    return false;
+                }
+                Decision::Workflow(next) => name = next,
+            }
+        }
+    }
+
+    /// The pieces of `part` that end up accepted.
+    pub fn find_accepted(&self, part: PartInterval) -> Vec<PartInterval> {
+        let mut stack = vec![(part, &self.workflows["in"])];
+        let mut result = vec![];
+        while let Some((part, workflow)) = stack.pop() {
+            for (part, decision) in workflow.process_interval(part) {
+                match decision {
+                    Decision::Accept => result.push(part),
+                    Decision::Reject => {}
+                    Decision::Workflow(name) => stack.push((part, &self.workflows[name])),
+                }
+            }
+        }
+        result
+    }
+
+    /// The number of distinct rating combinations in `part` that end up accepted, a `u64` under
+    /// `policy`.
+    pub fn count_accepted(&self, part: PartInterval, policy: Policy) -> Result<i128> {
+        let combinations = self
+            .find_accepted(part)
+            .iter()
+            .map(|accepted| {
+                let lengths = accepted
+                    .axes
+                    .iter()
+                    .map(|axis| i128::from(axis.end.saturating_sub(axis.start)));
+                policy.product::<u64>(lengths, "accepted combinations")
+            })
+            .collect::<Result<Vec<_>>>()?;
+        policy.sum::<u64>(combinations, "sum of accepted combinations")
+    }
+
+    /// Parses the workflows in `system`, a slice of `input`. They must start at `in`, only send
+    /// parts to workflows that exist, and never send a part back to a workflow it went through.
+    pub fn parse(input: &str, system: &'a str) -> Result<Self, ParseError> {
+        let mut workflows = HashMap::new();
+        let mut names = vec![];
+        for line in system.lines() {
+            let (name, workflow) = parse::split_once(input, line, "{")?;
+            let workflow = parse::strip_suffix(input, workflow, "}")?;
+            if workflows
+                .insert(name, Workflow::parse(input, workflow)?)
+                .is_some()
+            {
+                return Err(ParseError::at(
+                    input,
+                    name,
+                    "a workflow name not used before",
+                ));
+            }
+            names.push(name);
+        }
+        if !workflows.contains_key("in") {
+            return Err(ParseError::at(input, system, "a workflow named \"in\""));
+        }
+        let system = Self { workflows };
+        for workflow in system.workflows.values() {
+            if let Some(name) = workflow
+                .next_workflows()
+                .find(|name| !system.workflows.contains_key(name))
+            {
+                return Err(ParseError::at(input, name, "the name of a workflow"));
+            }
+        }
+        system.check_acyclic(input, &names)?;
+        Ok(system)
+    }
+
+    /// Fails at the first decision found that sends parts back to a workflow they went through,
+    /// searching depth first from every one of `names` in turn.
+    fn check_acyclic(&self, input: &str, names: &[&'a str]) -> Result<(), ParseError> {
+        // Whether every way on from a workflow was searched, for the workflows reached so far.
+        let mut searched = HashMap::new();
+        for &start in names {
+            if searched.contains_key(start) {
+                continue;
+            }
+            searched.insert(start, false);
+            let mut path = vec![(start, self.workflows[start].next_workflows())];
+            while let Some((name, next_workflows)) = path.last_mut() {
+                let name = *name;
+                match next_workflows.next() {
+                    Some(next) => match searched.get(next) {
+                        Some(false) => {
+                            let expected = "a workflow the part didn't go through";
+                            return Err(ParseError::at(input, next, expected));
+                        }
+                        Some(true) => {}
+                        None => {
+                            searched.insert(next, false);
+                            path.push((next, self.workflows[next].next_workflows()));
+                        }
+                    },
+                    None => {
+                        searched.insert(name, true);
+                        path.pop();
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Parses a category, which must be one of `x`, `m`, `a` and `s`, at the start of `token`.
+fn parse_key(input: &str, token: &str) -> Result<u8, ParseError> {
+    match token.as_bytes().first() {
+        Some(&key) if b"xmas".contains(&key) => Ok(key),
+        _ => Err(ParseError::at(input, token, "'x', 'm', 'a' or 's'")),
+    }
+}
+
+/// Parses something like "{x=787,m=2655,a=1222,s=2876}".
+pub fn parse_part(input: &str, line: &str) -> Result<Part, ParseError> {
+    let mut result = Part::new();
+    let part = parse::delimited(input, line, "{", "}")?;
+    parse::record(input, part, ",", "=", |key, value| {
+        if key.len() != 1 {
+            return Err(ParseError::at(input, key, "'x', 'm', 'a' or 's'"));
+        }
+        if result
+            .insert(parse_key(input, key)?, parse::number(input, value)?)
+            .is_some()
+        {
+            return Err(ParseError::at(input, key, "a category not rated before"));
+        }
+        Ok(())
+    })?;
+    if result.len() < CATEGORIES.len() {
+        return Err(ParseError::at(input, line, "ratings of x, m, a and s"));
+    }
+    Ok(result)
+}
+
+/// Parses the workflows and, after a blank line, the parts.
+pub fn parse(input: &str) -> Result<(System<'_>, Vec<Part>), ParseError> {
+    let [system, parts] = parse::sections(input, input)?;
+    let system = System::parse(input, system)?;
+    let parts = parts
+        .lines()
+        .map(|line| parse_part(input, line))
+        .collect::<Result<_, _>>()?;
+    Ok((system, parts))
+}
+
+/// Sums all ratings of the accepted `parts`, kept in `u32`s.
+pub fn run(system: &System, parts: &[Part], policy: Policy) -> Result<i128> {
+    run_traced(system, parts, policy, &mut Ignore)
+}
+
+/// Like [`run`], reporting the way of every part to `observer`.
+pub fn run_traced(
+    system: &System,
+    parts: &[Part],
+    policy: Policy,
+    observer: &mut impl for<'e> Observer<Event<'e>>,
+) -> Result<i128> {
+    let mut ratings = vec![];
+    for part in parts {
+        if system.process_traced(part, observer) {
+            let values = part.values().map(|&value| value.into());
+            ratings.push(policy.sum::<u32>(values, "ratings of a part")?);
+        }
+    }
+    policy.sum::<u32>(ratings, "sum of accepted ratings")
+}
+
+/// Generates `size` workflows and `size` parts. Workflows only send parts to workflows generated
+/// after them, so every part ends up accepted or rejected.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let name = |index: usize| -> String {
+        if index == 0 {
+            return "in".to_string();
+        }
+        // Three letters, so never "in".
+        (0..3)
+            .rev()
+            .map(|digit| (b'a' + (index / 26usize.pow(digit) % 26) as u8) as char)
+            .collect()
+    };
+    let decision = |rng: &mut Rng, index: usize| match rng.index(size - index + 1) {
+        0 => "A".to_string(),
+        1 => "R".to_string(),
+        next => name(index + next - 1),
+    };
+    let mut result = String::new();
+    for index in 0..size {
+        let mut rules = vec![];
+        for _ in 0..rng.index(4) {
+            rules.push(format!(
+                "{}{}{}:{}",
+                *rng.choose(&CATEGORIES) as char,
+                rng.choose(&['<', '>']),
+                rng.range(1..4001),
+                decision(rng, index)
+            ));
+        }
+        rules.push(decision(rng, index));
+        result += &format!("{}{{{}}}\n", name(index), rules.join(","));
+    }
+    result.push('\n');
+    for _ in 0..size {
+        let ratings = CATEGORIES.map(|key| format!("{}={}", key as char, rng.range(1..4001)));
+        result += &format!("{{{}}}\n", ratings.join(","));
+    }
+    result
+}
+
+/// The solution of the day's puzzle.
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 19;
+
+    type Input<'a> = (System<'a>, Vec<Part>);
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        Ok(parse(input)?)
+    }
+
+    fn part1((system, parts): &Self::Input<'_>) -> Result<Answer> {
+        run(system, parts, arith::policy()).map(Answer::from)
+    }
+
+    fn part2((system, _): &Self::Input<'_>) -> Result<Answer> {
+        system
+            .count_accepted(all_parts(), arith::policy())
+            .map(Answer::from)
+    }
+
+    fn run_traced(
+        input: &Self::Input<'_>,
+        part: aoc_common::Part,
+        tracer: &mut Tracer,
+    ) -> Result<Answer> {
+        match part {
+            aoc_common::Part::One => {
+                run_traced(&input.0, &input.1, arith::policy(), tracer).map(Answer::from)
+            }
+            aoc_common::Part::Two => Self::part2(input),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{input::fixture, random, solution::answer, Error, Part};
+
+    use super::*;
+
+    fn example() -> String {
+        fixture(env!("CARGO_MANIFEST_DIR"), "example.txt")
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(
+            answer::<Day>(&example(), Part::One),
+            Ok(Answer::Integer(19114))
+        );
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(
+            answer::<Day>(&example(), Part::Two),
+            Ok(Answer::Integer(167409079868000))
+        );
+    }
+
+    fn interval(key: u8, range: std::ops::Range<u32>) -> PartInterval {
+        all_parts().with_axis(axis(key), range.into())
+    }
+
+    #[test]
+    fn overflowing_ratings() {
+        let input = "in{A}\n\n{x=4294967295,m=0,a=0,s=0}\n{x=1,m=0,a=0,s=0}\n";
+        let (system, parts) = parse(input).unwrap();
+        let max = i128::from(u32::MAX);
+        assert_eq!(run(&system, &parts[..1], Policy::Checked), Ok(max));
+        assert_eq!(
+            run(&system, &parts, Policy::Checked),
+            Err(Error::Overflow("sum of accepted ratings".to_string()))
+        );
+        assert_eq!(run(&system, &parts, Policy::Saturating), Ok(max));
+        assert_eq!(run(&system, &parts, Policy::Wide), Ok(max + 1));
+    }
+
+    #[test]
+    fn overflowing_combinations() {
+        let system = System::parse("in{A}", "in{A}").unwrap();
+        let half = Interval::new(0, 1 << 31);
+        let parts = Cuboid::new([half, half, half, Interval::new(0, 2)]);
+        assert_eq!(
+            system.count_accepted(parts, Policy::Checked),
+            Err(Error::Overflow("accepted combinations".to_string()))
+        );
+        assert_eq!(
+            system.count_accepted(parts, Policy::Saturating),
+            Ok(u64::MAX.into())
+        );
+        assert_eq!(system.count_accepted(parts, Policy::Wide), Ok(1 << 94));
+    }
+
+    #[test]
+    fn invalid_systems() {
+        let error = |input: &str| {
+            let error = parse(input).unwrap_err();
+            (error.line, error.column, error.expected)
+        };
+        let part = "\n\n{x=1,m=2,a=3,s=4}\n";
+        assert_eq!(
+            error(&format!("in{{x<2:ab,A}}{}", part)),
+            (1, 8, "the name of a workflow".to_string())
+        );
+        assert_eq!(
+            error(&format!("ab{{A}}{}", part)),
+            (1, 1, "a workflow named \"in\"".to_string())
+        );
+        assert_eq!(
+            error(&format!("in{{ab}}\nab{{x>1:in,R}}{}", part)),
+            (2, 8, "a workflow the part didn't go through".to_string())
+        );
+        assert_eq!(
+            error(&format!("in{{in}}{}", part)),
+            (1, 4, "a workflow the part didn't go through".to_string())
+        );
+        assert_eq!(
+            error(&format!("in{{A}}\nin{{R}}{}", part)),
+            (2, 1, "a workflow name not used before".to_string())
+        );
+        assert_eq!(
+            error("in{A}\n\n{x=1,m=2,a=3}\n"),
+            (3, 1, "ratings of x, m, a and s".to_string())
+        );
+        assert_eq!(
+            error("in{A}\n\n{x=1,m=2,a=3,x=4}\n"),
+            (3, 14, "a category not rated before".to_string())
+        );
+        // Workflows reached along two ways are not a cycle.
+        assert!(parse(&format!("in{{x>1:ab,cd}}\nab{{cd}}\ncd{{A}}{}", part)).is_ok());
+    }
+
+    #[test]
+    fn rule_process_interval() {
+        let rule = "a<2006:qkq";
+        let rule = Rule::parse(rule, rule).unwrap();
+        let decision = Decision::Workflow("qkq");
+        assert_eq!(
+            rule.process_interval(interval(b'a', 1..4001)),
+            (
+                Some((interval(b'a', 1..2006), decision)),
+                Some(interval(b'a', 2006..4001))
+            )
+        );
+        assert_eq!(
+            rule.process_interval(interval(b'a', 1..100)),
+            (Some((interval(b'a', 1..100), decision)), None)
+        );
+        assert_eq!(
+            rule.process_interval(interval(b'a', 2006..4001)),
+            (None, Some(interval(b'a', 2006..4001)))
+        );
+
+        let rule = "m>2090:A";
+        let rule = Rule::parse(rule, rule).unwrap();
+        assert_eq!(
+            rule.process_interval(interval(b'm', 1..4001)),
+            (
+                Some((interval(b'm', 2091..4001), Decision::Accept)),
+                Some(interval(b'm', 1..2091))
+            )
+        );
+    }
+
+    #[test]
+    fn accepted_parts() {
+        let input = example();
+        let (system, parts) = Day::parse(&input).unwrap();
+        let accepted = parts
+            .iter()
+            .map(|part| system.process(part))
+            .collect::<Vec<_>>();
+        assert_eq!(accepted, [true, false, true, false, true]);
+    }
+
+    #[test]
+    fn generated_parts_agree_with_intervals() {
+        for seed in 0..50 {
+            let input = generate(&mut Rng::new(seed), 10);
+            let (system, parts) = parse(&input).unwrap();
+            let accepted = system.find_accepted(all_parts());
+            for part in &parts {
+                let point = CATEGORIES.map(|key| part[&key]);
+                assert_eq!(
+                    system.process(part),
+                    accepted.iter().any(|interval| interval.contains(point)),
+                    "{}",
+                    input
+                );
+            }
+        }
+    }
+
+    /// Small boxes of parts around a corner of the first few accepted pieces, picking the side of
+    /// every axis that a rule cut off, if any.
+    fn probes(system: &System) -> Vec<PartInterval> {
+        let mut accepted = system.find_accepted(all_parts());
+        accepted.truncate(3);
+        accepted
+            .into_iter()
+            .map(|piece| {
+                Cuboid::new(piece.axes.map(|interval| {
+                    let cut = if interval.start > 1 {
+                        interval.start
+                    } else {
+                        interval.end
+                    };
+                    let start = cut.clamp(4, 3998) - 3;
+                    Interval::new(start, start + 6)
+                }))
+            })
+            .collect()
+    }
+
+    fn checked_count(system: &System, part: PartInterval) -> u64 {
+        system.count_accepted(part, Policy::Checked).unwrap() as u64
+    }
+
+    #[test]
+    fn agrees_with_reference() {
+        let count = |input: &str, count: fn(&System, PartInterval) -> u64| {
+            let (system, _) = parse(input).unwrap();
+            probes(&system)
+                .into_iter()
+                .map(|part| count(&system, part))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            count(&example(), checked_count),
+            count(&example(), reference::count_accepted)
+        );
+        random::assert_agree(
+            50,
+            |rng| generate(rng, 10),
+            |input| count(input, checked_count),
+            |input| count(input, reference::count_accepted),
+        );
+    }
+}
@@ -0,0 +1,25 @@
+//! Slow but simple solutions to check the fast ones against.
+
+use aoc_common::Interval;
+
+use super::{Part, PartInterval, System, CATEGORIES};
+
+/// Counts the accepted parts in `part` by sending every one of them through the workflows. Only
+/// for small intervals.
+pub fn count_accepted(system: &System, part: PartInterval) -> u64 {
+    let [x, m, a, s] = part.axes.map(Interval::to_range);
+    let mut result = 0;
+    for x in x {
+        for m in m.clone() {
+            for a in a.clone() {
+                for s in s.clone() {
+                    let part = CATEGORIES.into_iter().zip([x, m, a, s]).collect::<Part>();
+                    if system.process(&part) {
+                        result += 1;
+                    }
+                }
+            }
+        }
+    }
+    result
+}
@@ -0,0 +1,183 @@
+//! 2023 day 2: Cube Conundrum.
+
+#![warn(missing_docs)]
+
+use aoc_common::{
+    arith::{self, Policy},
+    parse, Answer, ParseError, Result, Solution,
+};
+
+/// A game of revealing cubes from the bag.
+pub struct Game {
+    /// The number the game is recorded under.
+    pub id: u32,
+    /// The rounds of the game, in order.
+    pub rounds: Vec<Round>,
+}
+
+/// The cubes revealed at once in a game.
+pub struct Round {
+    /// Red cubes revealed.
+    pub red: u32,
+    /// Blue cubes revealed.
+    pub blue: u32,
+    /// Green cubes revealed.
+    pub green: u32,
+}
+
+/// How many cubes of each color are in the bag.
+pub struct Bag {
+    /// Red cubes in the bag.
+    pub red: u32,
+    /// Blue cubes in the bag.
+    pub blue: u32,
+    /// Green cubes in the bag.
+    pub green: u32,
+}
+
+/// Whether `bag` holds enough cubes of every color to reveal `round`.
+pub fn round_is_possible(bag: &Bag, round: &Round) -> bool {
+    bag.red >= round.red && bag.blue >= round.blue && bag.green >= round.green
+}
+
+/// Whether every round of `game` could be drawn from `bag`.
+pub fn game_is_possible(bag: &Bag, game: &Game) -> bool {
+    for round in &game.rounds {
+        if !round_is_possible(bag, round) {
+            return false;
+        }
+    }
+    true
+}
+
+/// The smallest bag every round of `game` could be drawn from.
+pub fn fewest_ball_bag(game: &Game) -> Bag {
+    let mut bag = Bag {
+        red: 0,
+        blue: 0,
+        green: 0,
+    };
+    for round in &game.rounds {
+        bag.red = bag.red.max(round.red);
+        bag.blue = bag.blue.max(round.blue);
+        bag.green = bag.green.max(round.green);
+    }
+    bag
+}
+
+/// The product of the cube counts of `bag`, as a `u32` under `policy`.
+pub fn bag_power(bag: &Bag, policy: Policy) -> Result<i128> {
+    let counts = [bag.red, bag.blue, bag.green].map(i128::from);
+    policy.product::<u32>(counts, "bag power")
+}
+
+/// Parses something like "Game 4: 4 blue, 8 green, 5 red; 6 red, 7 blue, 9 green; 2 green, 2 red, 2 blue; 2 green, 6 blue, 9 red; 10 red, 9 green"
+pub fn parse_game(input: &str, line: &str) -> Result<Game, ParseError> {
+    let (id, rounds) = parse::split_once(input, line, ": ")?;
+    let id = parse::number(input, parse::strip_prefix(input, id, "Game ")?)?;
+    let rounds = parse::separated(rounds, "; ", |round| {
+        let mut result = Round {
+            red: 0,
+            blue: 0,
+            green: 0,
+        };
+        parse::record(input, round, ", ", " ", |count, name| {
+            let count = parse::number(input, count)?;
+            match name {
+                "red" => result.red = count,
+                "blue" => result.blue = count,
+                "green" => result.green = count,
+                _ => return Err(ParseError::at(input, name, "color")),
+            }
+            Ok(())
+        })?;
+        Ok(result)
+    })?;
+    Ok(Game { id, rounds })
+}
+
+/// The solution of the day's puzzle.
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 2;
+
+    type Input<'a> = Vec<Game>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        Ok(input
+            .lines()
+            .map(|line| parse_game(input, line))
+            .collect::<Result<_, _>>()?)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer> {
+        let bag = Bag {
+            red: 12,
+            blue: 14,
+            green: 13,
+        };
+        let ids = input
+            .iter()
+            .filter(|game| game_is_possible(&bag, game))
+            .map(|game| i128::from(game.id));
+        Ok(arith::policy().sum::<u32>(ids, "sum of game ids")?.into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer> {
+        let policy = arith::policy();
+        let powers = input
+            .iter()
+            .map(|game| bag_power(&fewest_ball_bag(game), policy))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(policy.sum::<u32>(powers, "sum of bag powers")?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{input::fixture, solution::answer, Error, Part};
+
+    use super::*;
+
+    fn example() -> String {
+        fixture(env!("CARGO_MANIFEST_DIR"), "example.txt")
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(answer::<Day>(&example(), Part::One), Ok(Answer::Integer(8)));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(
+            answer::<Day>(&example(), Part::Two),
+            Ok(Answer::Integer(2286))
+        );
+    }
+
+    #[test]
+    fn fewest_balls() {
+        let games = Day::parse("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green").unwrap();
+        let bag = fewest_ball_bag(&games[0]);
+        assert_eq!((bag.red, bag.green, bag.blue), (4, 2, 6));
+        assert_eq!(bag_power(&bag, Policy::Checked), Ok(48));
+    }
+
+    #[test]
+    fn overflowing_power() {
+        let bag = Bag {
+            red: 70000,
+            blue: 70000,
+            green: 1,
+        };
+        assert_eq!(
+            bag_power(&bag, Policy::Checked),
+            Err(Error::Overflow("bag power".to_string()))
+        );
+        assert_eq!(bag_power(&bag, Policy::Saturating), Ok(u32::MAX.into()));
+        assert_eq!(bag_power(&bag, Policy::Wide), Ok(4_900_000_000));
+    }
+}
@@ -0,0 +1,323 @@
+//! 2023 day 22: Sand Slabs.
+
+#![warn(missing_docs)]
+
+use std::{
+    collections::HashSet,
+    fmt::{Display, Formatter},
+};
+
+use aoc_common::{
+    arith::{self, Policy},
+    parallel, parse,
+    random::Rng,
+    Answer, Interval, ParseError, Result, Solution,
+};
+use daggy::{
+    petgraph::{
+        visit::{Bfs, EdgeRef, IntoEdgesDirected, IntoNodeIdentifiers},
+        Direction,
+    },
+    Dag, NodeIndex, Walker,
+};
+
+const MAX_SIDE: usize = 1 << 10;
+const MAX_HEIGHT: u64 = 1 << 32;
+
+/// A brick of sand, spanning a non-empty interval on every axis.
+#[derive(Debug, Clone, Copy)]
+pub struct Brick {
+    /// The cubes the brick spans along x.
+    pub x: Interval<usize>,
+    /// The cubes the brick spans along y.
+    pub y: Interval<usize>,
+    /// The cubes the brick spans along z, upwards from the ground.
+    pub z: Interval<usize>,
+}
+
+impl Brick {
+    /// Parses something like "1,0,1~1,2,1".
+    pub fn parse(input: &str, line: &str) -> Result<Brick, ParseError> {
+        let (lower, upper) = parse::split_once(input, line, "~")?;
+        let [lower_x, lower_y, lower_z] = parse_coord(input, lower)?;
+        let [upper_x, upper_y, upper_z] = parse_coord(input, upper)?;
+        let interval = |first, last| {
+            Interval::checked_inclusive(first, last)
+                .ok_or_else(|| ParseError::at(input, upper, "coordinates below the largest usize"))
+        };
+        let brick = Brick {
+            x: interval(lower_x, upper_x)?,
+            y: interval(lower_y, upper_y)?,
+            z: interval(lower_z, upper_z)?,
+        };
+        if brick.x.is_empty() || brick.y.is_empty() || brick.z.is_empty() {
+            return Err(ParseError::at(
+                input,
+                upper,
+                "corner not below the first one",
+            ));
+        }
+        // The settled heights are kept in a map of every x and y, and added up along z.
+        if brick.x.end > MAX_SIDE || brick.y.end > MAX_SIDE {
+            return Err(ParseError::at(
+                input,
+                line,
+                "x and y coordinates below 1024",
+            ));
+        }
+        if brick.z.end as u64 > MAX_HEIGHT {
+            return Err(ParseError::at(input, line, "z coordinates below 2^32"));
+        }
+        Ok(brick)
+    }
+}
+
+/// Prints the brick the way it is parsed, like "1,0,1~1,2,1".
+impl Display for Brick {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{},{},{}~{},{},{}",
+            self.x.start,
+            self.y.start,
+            self.z.start,
+            self.x.end - 1,
+            self.y.end - 1,
+            self.z.end - 1
+        )
+    }
+}
+
+impl Brick {
+    fn overlaps(&self, other: &Brick) -> bool {
+        self.x.intersection(&other.x).is_some()
+            && self.y.intersection(&other.y).is_some()
+            && self.z.intersection(&other.z).is_some()
+    }
+}
+
+/// Generates a snapshot of up to `size` bricks, each up to 3 cubes long, that don't overlap and
+/// float up to `2 * size` above the ground.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let side = 3 + size / 10;
+    let mut bricks: Vec<Brick> = vec![];
+    for _ in 0..size * 10 {
+        if bricks.len() == size {
+            break;
+        }
+        let mut start = [rng.index(side), rng.index(side), 1 + rng.index(2 * size)];
+        let mut end = start;
+        let axis = rng.index(3);
+        end[axis] += rng.index(3);
+        if axis < 2 && end[axis] >= side {
+            start[axis] -= end[axis] + 1 - side;
+            end[axis] = side - 1;
+        }
+        let brick = Brick {
+            x: Interval::inclusive(start[0], end[0]),
+            y: Interval::inclusive(start[1], end[1]),
+            z: Interval::inclusive(start[2], end[2]),
+        };
+        if !bricks.iter().any(|other| other.overlaps(&brick)) {
+            bricks.push(brick);
+        }
+    }
+    bricks.iter().map(|brick| format!("{}\n", brick)).collect()
+}
+
+fn parse_coord(input: &str, coord: &str) -> Result<[usize; 3], ParseError> {
+    parse::separated_n(input, coord, ",", |axis| parse::number(input, axis))
+}
+
+/// Settles the bricks and returns the graph of which brick supports which, along with the ground node.
+pub fn build_support_graph(bricks: &[Brick]) -> (Dag<(), ()>, NodeIndex) {
+    let mut bricks = bricks.to_vec();
+    bricks.sort_by_key(|brick| brick.z.start);
+    let max_x = bricks.iter().map(|b| b.x.end).max().unwrap_or(0);
+    let max_y = bricks.iter().map(|b| b.y.end).max().unwrap_or(0);
+
+    let mut support_graph = Dag::<(), ()>::new();
+    let ground_node = support_graph.add_node(());
+    let mut map = vec![vec![(0usize, ground_node); max_x]; max_y];
+
+    for brick in bricks {
+        // Find the support height and support nodes.
+        let mut support_height = 0;
+        let mut support_nodes = [ground_node].into_iter().collect::<HashSet<NodeIndex>>();
+        for row in &map[brick.y.to_range()] {
+            for &(test_height, test_node) in &row[brick.x.to_range()] {
+                if test_height == support_height {
+                    support_nodes.insert(test_node);
+                } else if test_height > support_height {
+                    support_height = test_height;
+                    support_nodes = [test_node].into_iter().collect::<HashSet<NodeIndex>>();
+                }
+            }
+        }
+
+        // Calculate new height and node.
+        let new_height = support_height + brick.z.end.saturating_sub(brick.z.start);
+        let new_node = support_graph.add_node(());
+
+        // Update map.
+        for row in &mut map[brick.y.to_range()] {
+            for cell in &mut row[brick.x.to_range()] {
+                *cell = (new_height, new_node);
+            }
+        }
+
+        // Update support graph.
+        for support_node in support_nodes {
+            support_graph.add_edge(support_node, new_node, ()).unwrap();
+        }
+    }
+
+    (support_graph, ground_node)
+}
+
+/// The solution of the day's puzzle.
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 22;
+
+    type Input<'a> = Vec<Brick>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        Ok(input
+            .lines()
+            .map(|line| Brick::parse(input, line))
+            .collect::<Result<_, _>>()?)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer> {
+        Ok(count_safe_to_disintegrate(input).into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer> {
+        count_chain_reaction_falls(input, arith::policy()).map(Answer::from)
+    }
+}
+
+/// The number of bricks that can be taken away without any other brick falling.
+pub fn count_safe_to_disintegrate(bricks: &[Brick]) -> usize {
+    let (support_graph, ground_node) = build_support_graph(bricks);
+    let mut count = 0;
+    for node in support_graph.node_identifiers() {
+        if node == ground_node {
+            continue;
+        }
+
+        if fallers(&support_graph, node).is_empty() {
+            count += 1;
+        }
+    }
+    count
+}
+
+/// Sums, over every brick, how many other bricks would fall if it were taken away.
+pub fn count_chain_reaction_falls(bricks: &[Brick], policy: Policy) -> Result<i128> {
+    let (support_graph, ground_node) = build_support_graph(bricks);
+    let num_bricks = support_graph.node_count() - 1;
+    let nodes = support_graph
+        .node_identifiers()
+        .filter(|&node| node != ground_node)
+        .collect::<Vec<_>>();
+    let falls = parallel::map(&nodes, |&node| {
+        let mut disintegrated = support_graph.clone();
+        disintegrated.remove_node(node);
+        let num_bricks_left = Bfs::new(&disintegrated, ground_node)
+            .iter(&disintegrated)
+            .count()
+            - 1;
+        (num_bricks - num_bricks_left - 1) as i128
+    });
+    policy.sum::<usize>(falls, "sum of falls")
+}
+
+/// The bricks supported by `node` alone.
+pub fn fallers(support_graph: &Dag<(), ()>, node: NodeIndex) -> Vec<NodeIndex> {
+    let mut result = Vec::new();
+    for edge in support_graph.edges_directed(node, Direction::Outgoing) {
+        if support_graph
+            .edges_directed(edge.target(), Direction::Incoming)
+            .count()
+            == 1
+        {
+            result.push(edge.target());
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{input::fixture, solution::answer, Error, Part};
+
+    use super::*;
+
+    fn example() -> String {
+        fixture(env!("CARGO_MANIFEST_DIR"), "example.txt")
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(answer::<Day>(&example(), Part::One), Ok(Answer::Integer(5)));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(answer::<Day>(&example(), Part::Two), Ok(Answer::Integer(7)));
+    }
+
+    #[test]
+    fn huge_coordinates_are_rejected() {
+        for input in [
+            "0,0,1~1024,0,1\n",
+            "0,0,1~0,1024,1\n",
+            "0,0,1~0,0,4294967296\n",
+        ] {
+            assert!(
+                matches!(Day::parse(input), Err(Error::Parse(_))),
+                "{}",
+                input
+            );
+        }
+        // The highest z allowed is beyond a 32-bit usize, where parsing rejects it anyway.
+        let highest = if cfg!(target_pointer_width = "64") {
+            "0,0,1~1023,1023,4294967295\n"
+        } else {
+            "0,0,1~1023,1023,4294967294\n"
+        };
+        assert!(Day::parse(highest).is_ok());
+        assert_eq!(answer::<Day>("", Part::One), Ok(Answer::Integer(0)));
+    }
+
+    #[test]
+    fn generated_answers_ignore_order_and_height() {
+        for seed in 0..30 {
+            let mut rng = Rng::new(seed);
+            let input = generate(&mut rng, 20);
+            let answers = |input: &str| Part::ALL.map(|part| answer::<Day>(input, part));
+            let expected = answers(&input);
+            assert!(expected.iter().all(Result::is_ok), "{}", input);
+
+            let mut lines = input.lines().collect::<Vec<_>>();
+            rng.shuffle(&mut lines);
+            assert_eq!(answers(&lines.join("\n")), expected, "{}", input);
+
+            let lifted = Day::parse(&input)
+                .unwrap()
+                .iter()
+                .map(|brick| {
+                    let z = Interval::new(brick.z.start + 5, brick.z.end + 5);
+                    Brick { z, ..*brick }.to_string()
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            assert_eq!(answers(&lifted), expected, "{}", input);
+        }
+    }
+}
@@ -0,0 +1,260 @@
+//! 2023 day 3: Gear Ratios.
+
+#![warn(missing_docs)]
+
+use aoc_common::{
+    arith::{self, Policy},
+    geometry::Position,
+    parse,
+    render::{Color, Picture},
+    Answer, Grid, ParseError, Part, Result, Solution,
+};
+
+/// The engine schematic.
+pub struct Matrix {
+    data: Grid<u8>,
+    numbers: Vec<Number>,
+}
+
+impl Matrix {
+    /// Parses the schematic, one ASCII character per cell. Every number must fit in a `u32`.
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let data = Grid::parse(input, "ASCII character", |c| u8::try_from(c).ok())?;
+        let mut numbers = Vec::new();
+        for (outer_index, line) in input.lines().enumerate() {
+            // The column and byte offset of the first digit of the number being read.
+            let mut start = None;
+            let end = [(line.len(), '.')];
+            for (column, (offset, c)) in line.char_indices().chain(end).enumerate() {
+                if c.is_ascii_digit() {
+                    start.get_or_insert((column, offset));
+                } else if let Some((first_column, first_offset)) = start.take() {
+                    numbers.push(Number {
+                        outer_index,
+                        inner_range: first_column..column,
+                        value: parse::number(input, &line[first_offset..offset])?,
+                    });
+                }
+            }
+        }
+        Ok(Self { data, numbers })
+    }
+
+    /// Every number in the schematic, row by row.
+    pub fn numbers(&self) -> &[Number] {
+        &self.numbers
+    }
+}
+
+/// A number in the schematic, spanning columns `inner_range` of row `outer_index`.
+pub struct Number {
+    /// The row of the number.
+    pub outer_index: usize,
+    /// The columns of its digits.
+    pub inner_range: std::ops::Range<usize>,
+    /// The value of the number.
+    pub value: u32,
+}
+
+impl Number {
+    fn is_inner_adjacent(&self, index: usize) -> bool {
+        (self.inner_range.start..self.inner_range.end + 1).contains(&index)
+            || (self.inner_range.start > 0 && self.inner_range.start - 1 == index)
+    }
+
+    /// The cells the number is written in.
+    pub fn cells(&self) -> impl Iterator<Item = Position> + '_ {
+        self.inner_range
+            .clone()
+            .map(|inner_index| (self.outer_index, inner_index))
+    }
+
+    /// Whether the cell at row `outer_index` and column `inner_index` touches the number,
+    /// diagonals included.
+    pub fn is_adjacent(&self, outer_index: usize, inner_index: usize) -> bool {
+        self.is_inner_adjacent(inner_index) && is_adjacent(self.outer_index, outer_index)
+    }
+}
+
+fn is_adjacent(num1: usize, num2: usize) -> bool {
+    if num1 > num2 {
+        num1 - num2 == 1
+    } else {
+        num2 - num1 <= 1
+    }
+}
+
+fn is_symbol(c: u8) -> bool {
+    c != b'.' && !c.is_ascii_digit()
+}
+
+/// The solution of the day's puzzle.
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 3;
+
+    type Input<'a> = Matrix;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        Ok(Matrix::parse(input)?)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer> {
+        Ok(part_number_sum(input, arith::policy())?.into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer> {
+        Ok(gear_ratio_sum(input, arith::policy())?.into())
+    }
+
+    fn render(input: &Self::Input<'_>, part: Part) -> Result<Option<Picture>> {
+        Ok(Some(render(input, part)))
+    }
+}
+
+/// The schematic with the part numbers highlighted, or for part 2 the gears and their numbers.
+pub fn render(matrix: &Matrix, part: Part) -> Picture {
+    let numbers = matrix.numbers();
+    let picture = Picture::new(matrix.data.map(|&c| char::from(c)));
+    match part {
+        Part::One => {
+            let part_numbers = numbers
+                .iter()
+                .filter(|number| is_part_number(matrix, number))
+                .flat_map(Number::cells);
+            picture.with_overlay("part numbers", Color::Green, part_numbers)
+        }
+        Part::Two => {
+            let gears = gears(matrix, numbers);
+            let gear_numbers = gears
+                .iter()
+                .flat_map(|(_, numbers)| numbers.iter().flat_map(|number| number.cells()));
+            picture
+                .with_overlay("gear numbers", Color::Green, gear_numbers)
+                .with_overlay("gears", Color::Red, gears.iter().map(|&(gear, _)| gear))
+        }
+    }
+}
+
+fn is_part_number(matrix: &Matrix, number: &Number) -> bool {
+    number.cells().any(|cell| {
+        matrix
+            .data
+            .neighbors8(cell)
+            .any(|position| is_symbol(matrix.data[position]))
+    })
+}
+
+/// Every `*` that touches exactly two numbers, with those numbers.
+fn gears<'a>(matrix: &Matrix, numbers: &'a [Number]) -> Vec<(Position, [&'a Number; 2])> {
+    let mut result = Vec::new();
+    for ((outer_index, inner_index), &c) in matrix.data.enumerate() {
+        if c == b'*' {
+            let mut adjacent_numbers = Vec::new();
+            for number in numbers {
+                if number.is_adjacent(outer_index, inner_index) {
+                    adjacent_numbers.push(number);
+                }
+            }
+            if let [a, b] = adjacent_numbers[..] {
+                result.push(((outer_index, inner_index), [a, b]));
+            }
+        }
+    }
+    result
+}
+
+/// Sums the numbers next to a symbol.
+pub fn part_number_sum(matrix: &Matrix, policy: Policy) -> Result<i128> {
+    let mut result = 0;
+    for number in matrix.numbers() {
+        if is_part_number(matrix, number) {
+            let value = i128::from(number.value);
+            result = policy.add::<u32>(result, value, "sum of part numbers")?;
+        }
+    }
+    Ok(result)
+}
+
+/// Sums the products of the two numbers next to each `*` that touches exactly two.
+pub fn gear_ratio_sum(matrix: &Matrix, policy: Policy) -> Result<i128> {
+    let mut result = 0;
+    for (_, [a, b]) in gears(matrix, matrix.numbers()) {
+        let ratio = policy.mul::<u32>(a.value.into(), b.value.into(), "gear ratio")?;
+        result = policy.add::<u32>(result, ratio, "sum of gear ratios")?;
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{input::fixture, solution::answer, Error, Part};
+
+    use super::*;
+
+    fn example() -> String {
+        fixture(env!("CARGO_MANIFEST_DIR"), "example.txt")
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(
+            answer::<Day>(&example(), Part::One),
+            Ok(Answer::Integer(4361))
+        );
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(
+            answer::<Day>(&example(), Part::Two),
+            Ok(Answer::Integer(467835))
+        );
+    }
+
+    #[test]
+    fn render_example() {
+        let matrix = Matrix::parse(&example()).unwrap();
+        let cells = |part| {
+            render(&matrix, part)
+                .overlays
+                .into_iter()
+                .map(|overlay| (overlay.name, overlay.cells.len()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(cells(Part::One), [("part numbers".to_string(), 23)]);
+        assert_eq!(
+            cells(Part::Two),
+            [("gear numbers".to_string(), 11), ("gears".to_string(), 2)]
+        );
+    }
+
+    #[test]
+    fn overflowing_gear_ratio() {
+        let matrix = Matrix::parse("70000*70000\n").unwrap();
+        assert_eq!(
+            gear_ratio_sum(&matrix, Policy::Checked),
+            Err(Error::Overflow("gear ratio".to_string()))
+        );
+        assert_eq!(gear_ratio_sum(&matrix, Policy::Wide), Ok(4_900_000_000));
+        let matrix = Matrix::parse("4000000000*4000000000\n").unwrap();
+        assert_eq!(
+            part_number_sum(&matrix, Policy::Checked),
+            Err(Error::Overflow("sum of part numbers".to_string()))
+        );
+        assert_eq!(
+            part_number_sum(&matrix, Policy::Saturating),
+            Ok(u32::MAX.into())
+        );
+    }
+
+    #[test]
+    fn too_long_number() {
+        let input = "1............\n.99999999999*\n";
+        let error = Matrix::parse(input).err().unwrap();
+        assert_eq!((error.line, error.column), (2, 2));
+        assert_eq!(error.expected, "number");
+    }
+}
@@ -0,0 +1,171 @@
+//! 2023 day 4: Scratchcards.
+
+#![warn(missing_docs)]
+
+use std::collections::BTreeMap;
+
+use aoc_common::{
+    arith::{self, Policy},
+    parse, Answer, ParseError, Result, Solution,
+};
+
+/// A scratchcard.
+pub struct Card {
+    /// The number of the card.
+    pub id: u32,
+    /// The numbers that win.
+    pub winning_numbers: Vec<u8>,
+    /// The numbers on the card.
+    pub numbers: Vec<u8>,
+}
+
+impl Card {
+    /// How many of our numbers are winning numbers.
+    pub fn winning_count(&self) -> u32 {
+        let mut count = 0;
+        for number in &self.numbers {
+            if self.winning_numbers.contains(number) {
+                count += 1;
+            }
+        }
+        count
+    }
+
+    /// 1 point for the first match, doubled for every other, as a `u32` under `policy`.
+    pub fn point(&self, policy: Policy) -> Result<i128> {
+        let count = self.winning_count();
+        if count > 0 {
+            policy.fit::<u32>(2i128.checked_pow(count - 1), "card points")
+        } else {
+            Ok(0)
+        }
+    }
+
+    /// Parse something like "Card   1: 95 57 30 62 11  5  9  3 72 87 | 94 72 74 98 23 57 62 14 30  3 73 49 80 96 20 60 17 35 11 63 87  9  6  5 95"
+    pub fn parse(input: &str, line: &str) -> Result<Card, ParseError> {
+        let (id, numbers) = parse::split_once(input, line, ":")?;
+        let id = parse::strip_prefix(input, id.trim(), "Card")?;
+        let id = parse::number(input, id.trim())?;
+        let (winning_numbers, numbers) = parse::split_once(input, numbers, "|")?;
+        Ok(Card {
+            id,
+            winning_numbers: parse::numbers(input, winning_numbers)?,
+            numbers: parse::numbers(input, numbers)?,
+        })
+    }
+}
+
+/// The solution of the day's puzzle.
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 4;
+
+    type Input<'a> = Vec<Card>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        Ok(input
+            .lines()
+            .map(|line| Card::parse(input, line))
+            .collect::<Result<_, _>>()?)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer> {
+        let policy = arith::policy();
+        let points = input
+            .iter()
+            .map(|card| card.point(policy))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(policy.sum::<u32>(points, "sum of points")?.into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer> {
+        Ok(count_cards(input, arith::policy())?.into())
+    }
+}
+
+/// The total number of cards once each card has won copies of the cards after it. Counts are
+/// `u32`s under `policy`.
+pub fn count_cards(input: &[Card], policy: Policy) -> Result<i128> {
+    let mut cards = BTreeMap::new();
+    for card in input {
+        cards.insert(card.id, (card, 1));
+    }
+
+    let card_ids = cards.keys().copied().collect::<Vec<_>>();
+    for card_id in card_ids {
+        let (card, count) = cards.get(&card_id).unwrap();
+        let winning_count = card.winning_count();
+        let count = *count;
+        for i in 1..=winning_count {
+            // Copies past the end of the table don't exist.
+            let won = card_id.checked_add(i).and_then(|id| cards.get_mut(&id));
+            if let Some((_, copies)) = won {
+                *copies = policy.add::<u32>(*copies, count, "card copies")?;
+            }
+        }
+    }
+    policy.sum::<u32>(cards.values().map(|&(_, count)| count), "total cards")
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{input::fixture, solution::answer, Error, Part};
+
+    use super::*;
+
+    fn example() -> String {
+        fixture(env!("CARGO_MANIFEST_DIR"), "example.txt")
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(
+            answer::<Day>(&example(), Part::One),
+            Ok(Answer::Integer(13))
+        );
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(
+            answer::<Day>(&example(), Part::Two),
+            Ok(Answer::Integer(30))
+        );
+    }
+
+    #[test]
+    fn card_points() {
+        let input = example();
+        let cards = Day::parse(&input).unwrap();
+        let points = cards
+            .iter()
+            .map(|card| card.point(Policy::Checked))
+            .collect::<Result<Vec<_>>>();
+        assert_eq!(points, Ok(vec![8, 2, 2, 1, 0, 0]));
+    }
+
+    #[test]
+    fn copies_past_the_table() {
+        let input = "Card 1: 1 2 | 1 2\nCard 2: 3 | 4\n";
+        assert_eq!(answer::<Day>(input, Part::Two), Ok(Answer::Integer(3)));
+    }
+
+    #[test]
+    fn overflowing_points() {
+        let numbers = (1..=40)
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let line = format!("Card 1: {} | {}", numbers, numbers);
+        let cards = Day::parse(&line).unwrap();
+        let card = &cards[0];
+        assert_eq!(
+            card.point(Policy::Checked),
+            Err(Error::Overflow("card points".to_string()))
+        );
+        assert_eq!(card.point(Policy::Saturating), Ok(u32::MAX.into()));
+        assert_eq!(card.point(Policy::Wide), Ok(1 << 39));
+    }
+}
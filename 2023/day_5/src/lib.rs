@@ -0,0 +1,427 @@
+//! 2023 day 5: If You Give A Seed A Fertilizer.
+
+#![warn(missing_docs)]
+
+use std::ops::Range;
+
+use aoc_common::{
+    arith::{self, Policy},
+    interval, parse,
+    random::Rng,
+    Answer, Error, Interval, ParseError, Result, Solution,
+};
+
+/// A map from one category to the next, leaving sources no entry covers unchanged.
+///
+/// Numbers are read as `u64`, and computed with as `i128` fitted into `u64` by a [`Policy`].
+pub struct Map {
+    /// The entries, where the first one covering a source maps it.
+    pub entries: Vec<Entry>,
+}
+
+impl Map {
+    /// The destination of `source`.
+    pub fn get(&self, source: i128, policy: Policy) -> Result<i128> {
+        for entry in &self.entries {
+            if let Some(destination) = entry.get(source, policy)? {
+                return Ok(destination);
+            }
+        }
+        Ok(source)
+    }
+
+    /// Maps every number in `source`, returning the destination ranges in no particular order.
+    pub fn get_range(&self, source: Range<i128>, policy: Policy) -> Result<Vec<Range<i128>>> {
+        let mut unmapped = vec![source];
+        let mut mapped = vec![];
+        for entry in &self.entries {
+            let mut new_unmapped = vec![];
+            for source in unmapped {
+                let (entry_new_unmapped, entry_mapped) = entry.get_range(source, policy)?;
+                new_unmapped.extend(entry_new_unmapped);
+                if let Some(entry_mapped) = entry_mapped {
+                    mapped.push(entry_mapped);
+                }
+            }
+            unmapped = new_unmapped;
+        }
+        mapped.extend(unmapped);
+        Ok(mapped)
+    }
+
+    /// Maps every number in `sources`, merging the destinations into disjoint sorted ranges.
+    pub fn get_all_ranges(
+        &self,
+        sources: Vec<Range<i128>>,
+        policy: Policy,
+    ) -> Result<Vec<Range<i128>>> {
+        let mut destinations = vec![];
+        for source in sources {
+            let mapped = self.get_range(source, policy)?;
+            destinations.extend(mapped.into_iter().map(Interval::from));
+        }
+        Ok(interval::normalize(destinations)
+            .into_iter()
+            .map(Interval::to_range)
+            .collect())
+    }
+
+    /// Parses the section of the map from category `from` to `to`, a slice of `input`: the
+    /// "`from`-to-`to` map:" header, then one entry per line.
+    pub fn parse(input: &str, section: &str, from: &str, to: &str) -> Result<Map, ParseError> {
+        let mut lines = section.lines();
+        let header = lines.next().unwrap_or_default();
+        let expected = format!("{}-to-{} map:", from, to);
+        if header != expected {
+            return Err(ParseError::at(input, header, format!("{:?}", expected)));
+        }
+        let entries = lines
+            .map(|line| Entry::parse(input, line))
+            .collect::<Result<_, _>>()?;
+        Ok(Map { entries })
+    }
+}
+
+/// The parts of a range an entry doesn't cover, and the destination of the part it does.
+pub type Split = (Vec<Range<i128>>, Option<Range<i128>>);
+
+/// Maps `length` numbers from `source_start` on to the same offsets from `destination_start`.
+pub struct Entry {
+    /// The destination of the first source.
+    pub destination_start: u64,
+    /// The first source mapped.
+    pub source_start: u64,
+    /// How many sources are mapped.
+    pub length: u64,
+}
+
+impl Entry {
+    /// The sources the entry maps.
+    pub fn source_range(&self, policy: Policy) -> Result<Range<i128>> {
+        range(self.source_start, self.length, policy, "entry source range")
+    }
+
+    /// The destination of `source`, or `None` if the entry doesn't cover it.
+    pub fn get(&self, source: i128, policy: Policy) -> Result<Option<i128>> {
+        if self.source_range(policy)?.contains(&source) {
+            self.destination(source, policy).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Where `source`, covered by the entry or just past its end, goes.
+    fn destination(&self, source: i128, policy: Policy) -> Result<i128> {
+        let offset = source - i128::from(self.source_start);
+        policy.add::<u64>(offset, self.destination_start.into(), "entry destination")
+    }
+
+    /// Splits `source` into the parts the entry doesn't cover and the destination of the part it
+    /// does.
+    pub fn get_range(&self, source: Range<i128>, policy: Policy) -> Result<Split> {
+        let source = Interval::from(source);
+        let source_range = Interval::from(self.source_range(policy)?);
+        let unmapped = source
+            .difference(&source_range)
+            .into_iter()
+            .map(Interval::to_range)
+            .collect();
+        let mapped = match source.intersection(&source_range) {
+            // The end is fitted as the last number mapped, which is what has to fit.
+            Some(mapped) => Some(
+                self.destination(mapped.start, policy)?
+                    ..self.destination(mapped.end - 1, policy)? + 1,
+            ),
+            None => None,
+        };
+        Ok((unmapped, mapped))
+    }
+
+    /// Parses "destination_start source_start length".
+    pub fn parse(input: &str, line: &str) -> Result<Entry, ParseError> {
+        let mut parts = line.split_whitespace();
+        let destination_start = parse::number(
+            input,
+            parse::next(input, line, &mut parts, "destination start")?,
+        )?;
+        let source_start =
+            parse::number(input, parse::next(input, line, &mut parts, "source start")?)?;
+        let length = parse::number(input, parse::next(input, line, &mut parts, "length")?)?;
+        Ok(Entry {
+            destination_start,
+            source_start,
+            length,
+        })
+    }
+}
+
+/// The `length` numbers from `start` on, where the last one is fitted into `u64` by `policy`.
+fn range(start: u64, length: u64, policy: Policy, step: &str) -> Result<Range<i128>> {
+    let start = i128::from(start);
+    if length == 0 {
+        return Ok(start..start);
+    }
+    let last = policy.add::<u64>(start, i128::from(length) - 1, step)?;
+    Ok(start..last + 1)
+}
+
+/// The seeds and the seven maps from seed to location.
+pub struct Almanac {
+    /// The numbers on the `seeds:` line.
+    pub seeds: Vec<u64>,
+    /// The maps, in the order they are applied.
+    pub maps: Vec<Map>,
+}
+
+impl Almanac {
+    /// Parses the seeds line followed by the map sections, one for each pair of consecutive
+    /// categories. The seeds come in pairs, read as ranges in part 2.
+    pub fn parse(input: &str) -> Result<Almanac, ParseError> {
+        let [seeds, sections @ ..] = parse::sections::<8>(input, input)?;
+        let seeds = parse::strip_prefix(input, seeds, "seeds:")?;
+        let tokens = seeds.split_whitespace().collect::<Vec<_>>();
+        if tokens.len() % 2 == 1 {
+            let last = tokens[tokens.len() - 1];
+            return Err(ParseError::at(input, last, "seeds in pairs"));
+        }
+        let seeds = tokens
+            .into_iter()
+            .map(|s| parse::number(input, s))
+            .collect::<Result<_, _>>()?;
+        let maps = sections
+            .into_iter()
+            .zip(CATEGORIES.windows(2))
+            .map(|(section, names)| Map::parse(input, section, names[0], names[1]))
+            .collect::<Result<_, _>>()?;
+        Ok(Almanac { seeds, maps })
+    }
+}
+
+/// The categories in order, each mapped to the next by one map.
+const CATEGORIES: [&str; 8] = [
+    "seed",
+    "soil",
+    "fertilizer",
+    "water",
+    "light",
+    "temperature",
+    "humidity",
+    "location",
+];
+
+/// Generates an almanac with `size` seed ranges and up to `size` entries per map, with all numbers
+/// below `100 * size`. The entries of a map never overlap.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1) as u64;
+    let limit = 100 * size;
+    let mut seeds = vec![];
+    for _ in 0..size {
+        let start = rng.range(0..limit);
+        seeds.push(start.to_string());
+        seeds.push(rng.range(1..limit - start + 1).to_string());
+    }
+    let mut result = format!("seeds: {}\n", seeds.join(" "));
+    for names in CATEGORIES.windows(2) {
+        let mut cuts = (0..size)
+            .map(|_| rng.range(0..limit))
+            .chain([0, limit])
+            .collect::<Vec<_>>();
+        cuts.sort();
+        cuts.dedup();
+        let mut entries = vec![];
+        for cut in cuts.windows(2) {
+            if rng.chance(1, 2) {
+                entries.push((rng.range(0..limit), cut[0], cut[1] - cut[0]));
+            }
+        }
+        if entries.is_empty() {
+            entries.push((rng.range(0..limit), cuts[0], cuts[1] - cuts[0]));
+        }
+        rng.shuffle(&mut entries);
+        result += &format!("\n{}-to-{} map:\n", names[0], names[1]);
+        for (destination_start, source_start, length) in entries {
+            result += &format!("{} {} {}\n", destination_start, source_start, length);
+        }
+    }
+    result
+}
+
+/// The solution of the day's puzzle.
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 5;
+
+    type Input<'a> = Almanac;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        Ok(Almanac::parse(input)?)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer> {
+        let policy = arith::policy();
+        let mut locations = vec![];
+        for &seed in &input.seeds {
+            let seed = i128::from(seed);
+            let location = input
+                .maps
+                .iter()
+                .try_fold(seed, |source, map| map.get(source, policy))?;
+            locations.push(location);
+        }
+        locations
+            .into_iter()
+            .min()
+            .map(Answer::from)
+            .ok_or_else(|| Error::NoSolution("no seeds".to_string()))
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer> {
+        let policy = arith::policy();
+        let seeds = input
+            .seeds
+            .chunks_exact(2)
+            .map(|chunk| range(chunk[0], chunk[1], policy, "seed range"))
+            .collect::<Result<Vec<_>>>()?;
+        let locations = input
+            .maps
+            .iter()
+            .try_fold(seeds, |sources, map| map.get_all_ranges(sources, policy))?;
+        locations
+            .iter()
+            .map(|range| range.start)
+            .min()
+            .map(Answer::from)
+            .ok_or_else(|| Error::NoSolution("no seeds".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{input::fixture, solution::answer, Part};
+
+    use super::*;
+
+    fn example() -> String {
+        fixture(env!("CARGO_MANIFEST_DIR"), "example.txt")
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(
+            answer::<Day>(&example(), Part::One),
+            Ok(Answer::Integer(35))
+        );
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(
+            answer::<Day>(&example(), Part::Two),
+            Ok(Answer::Integer(46))
+        );
+    }
+
+    #[test]
+    #[allow(clippy::single_range_in_vec_init)]
+    fn entry_get_range() {
+        // Maps 50..98 to 52..100.
+        let entry = Entry {
+            destination_start: 52,
+            source_start: 50,
+            length: 48,
+        };
+        let get_range = |source| entry.get_range(source, Policy::Checked).unwrap();
+        assert_eq!(get_range(79..93), (vec![], Some(81..95)));
+        assert_eq!(get_range(90..100), (vec![98..100], Some(92..100)));
+        assert_eq!(get_range(40..60), (vec![40..50], Some(52..62)));
+        assert_eq!(get_range(40..100), (vec![40..50, 98..100], Some(52..100)));
+        assert_eq!(get_range(0..10), (vec![0..10], None));
+    }
+
+    #[test]
+    #[allow(clippy::single_range_in_vec_init)]
+    fn map_get_range() {
+        let input = example();
+        let almanac = Day::parse(&input).unwrap();
+        let mut ranges = almanac.maps[0].get_range(79..93, Policy::Checked).unwrap();
+        ranges.sort_by_key(|range| range.start);
+        assert_eq!(ranges, [81..95]);
+        assert_eq!(almanac.maps[0].get(98, Policy::Checked), Ok(50));
+        assert_eq!(almanac.maps[0].get(10, Policy::Checked), Ok(10));
+    }
+
+    #[test]
+    fn malformed_almanacs_are_rejected() {
+        let example = example();
+        let odd_seeds = example.replacen("13\n", "13 7\n", 1);
+        let wrong_header = example.replacen("soil-to-fertilizer", "soil-to-water", 1);
+        let missing_map = example[..example.find("\nhumidity-to-location").unwrap()].to_string();
+        for (input, line, column) in [
+            (odd_seeds, 1, 20),
+            (wrong_header, 7, 1),
+            (missing_map, 30, 1),
+        ] {
+            let Err(Error::Parse(error)) = Day::parse(&input) else {
+                panic!("{:?} parsed", input);
+            };
+            assert_eq!((error.line, error.column), (line, column), "{}", error);
+        }
+    }
+
+    #[test]
+    #[allow(clippy::single_range_in_vec_init)]
+    fn overflowing_entries() {
+        let max = i128::from(u64::MAX);
+        let entry = Entry {
+            destination_start: u64::MAX - 1,
+            source_start: 10,
+            length: 5,
+        };
+        let overflow = Err(Error::Overflow("entry destination".to_string()));
+        assert_eq!(entry.get(11, Policy::Checked), Ok(Some(max)));
+        assert_eq!(entry.get(12, Policy::Checked), overflow);
+        assert_eq!(entry.get(12, Policy::Saturating), Ok(Some(max)));
+        assert_eq!(entry.get(12, Policy::Wide), Ok(Some(max + 1)));
+
+        let entry = Entry {
+            destination_start: 0,
+            source_start: u64::MAX,
+            length: 2,
+        };
+        let overflow = Err(Error::Overflow("entry source range".to_string()));
+        assert_eq!(entry.get(0, Policy::Checked), overflow);
+        assert_eq!(entry.source_range(Policy::Wide), Ok(max..max + 2));
+        assert_eq!(entry.source_range(Policy::Saturating), Ok(max..max + 1));
+
+        // The last number mapped fits, even if the end of the range doesn't.
+        let entry = Entry {
+            destination_start: u64::MAX - 1,
+            source_start: u64::MAX - 1,
+            length: 2,
+        };
+        let mapped = entry.get_range(0..max + 1, Policy::Checked);
+        assert_eq!(mapped, Ok((vec![0..max - 1], Some(max - 1..max + 1))));
+    }
+
+    #[test]
+    fn part2_matches_seed_by_seed_on_generated() {
+        for seed in 0..50 {
+            let input = generate(&mut Rng::new(seed), 4);
+            let almanac = Day::parse(&input).unwrap();
+            let expected = almanac
+                .seeds
+                .chunks_exact(2)
+                .flat_map(|chunk| chunk[0]..chunk[0] + chunk[1])
+                .map(|seed| {
+                    almanac.maps.iter().fold(i128::from(seed), |source, map| {
+                        map.get(source, Policy::Checked).unwrap()
+                    })
+                })
+                .min()
+                .unwrap();
+            assert_eq!(Day::part2(&almanac), Ok(expected.into()), "{}", input);
+        }
+    }
+}
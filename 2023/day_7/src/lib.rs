@@ -0,0 +1,237 @@
+//! 2023 day 7: Camel Cards.
+
+#![warn(missing_docs)]
+
+use std::{cmp::Ordering, collections::HashMap};
+
+use aoc_common::{
+    arith::{self, Policy},
+    parse, Answer, ParseError, Result, Solution,
+};
+
+/// Five cards valued 2 to 14 (ace), where 1 is a joker. Hands order by type, then card by card.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Hand(pub [u8; 5]);
+
+/// The type of a hand, from weakest to strongest.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Type {
+    /// All labels distinct.
+    HighCard,
+    /// Two cards share a label.
+    OnePair,
+    /// Two pairs of cards share labels.
+    TwoPair,
+    /// Three cards share a label.
+    ThreeOfAKind,
+    /// Three cards share a label, and the other two another one.
+    FullHouse,
+    /// Four cards share a label.
+    FourOfAKind,
+    /// All five cards share a label.
+    FiveOfAKind,
+}
+
+const JACK: u8 = 11;
+const JOKER: u8 = 1;
+
+impl Hand {
+    /// Reads every `J` as a jack instead of a joker.
+    pub fn with_jacks(self) -> Hand {
+        Hand(self.0.map(|card| if card == JOKER { JACK } else { card }))
+    }
+
+    /// The strongest type the hand makes, with every joker standing in for the most common card.
+    pub fn typ(&self) -> Type {
+        let mut counts = HashMap::new();
+        let mut num_jokers = 0;
+        for card in self.0 {
+            if card == JOKER {
+                num_jokers += 1;
+            } else {
+                *counts.entry(card).or_default() += 1;
+            }
+        }
+        let mut counts = counts.values().copied().collect::<Vec<_>>();
+        counts.sort();
+        if let Some(max_non_joker) = counts.last_mut() {
+            *max_non_joker += num_jokers;
+        } else {
+            counts = vec![5];
+        }
+        match counts.as_slice() {
+            [1, 1, 1, 1, 1] => Type::HighCard,
+            [1, 1, 1, 2] => Type::OnePair,
+            [1, 2, 2] => Type::TwoPair,
+            [1, 1, 3] => Type::ThreeOfAKind,
+            [2, 3] => Type::FullHouse,
+            [1, 4] => Type::FourOfAKind,
+            [5] => Type::FiveOfAKind,
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl PartialOrd for Hand {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Hand {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match self.typ().cmp(&other.typ()) {
+            Ordering::Equal => self.0.cmp(&other.0),
+            other => other,
+        }
+    }
+}
+
+/// Parses something like "TQA26 14", reading `J` as a joker.
+pub fn parse_bid(input: &str, line: &str) -> Result<(Hand, usize), ParseError> {
+    let mut bid = line.split_whitespace();
+    let cards = parse::next(input, line, &mut bid, "hand")?;
+    let hand = cards
+        .char_indices()
+        .map(|(index, c)| match c {
+            'T' => Ok(10),
+            'J' => Ok(JOKER),
+            'Q' => Ok(12),
+            'K' => Ok(13),
+            'A' => Ok(14),
+            '2'..='9' => Ok(c as u8 - b'0'),
+            _ => Err(ParseError::at(input, &cards[index..], "card")),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let hand = Hand(
+        hand.try_into()
+            .map_err(|_| ParseError::at(input, cards, "5 cards"))?,
+    );
+    let amount = parse::number(input, parse::next(input, line, &mut bid, "bid")?)?;
+    if let Some(extra) = bid.next() {
+        return Err(ParseError::at(input, extra, "end of line"));
+    }
+    Ok((hand, amount))
+}
+
+/// Sums each bid times the rank of its hand, the weakest hand ranking 1.
+pub fn total_winnings(mut bids: Vec<(Hand, usize)>, policy: Policy) -> Result<i128> {
+    bids.sort_by_key(|(hand, _)| *hand);
+    let winnings = bids
+        .iter()
+        .enumerate()
+        .map(|(i, &(_, bid))| policy.mul::<usize>(i as i128 + 1, bid as i128, "winnings"))
+        .collect::<Result<Vec<_>>>()?;
+    policy.sum::<usize>(winnings, "total winnings")
+}
+
+/// The solution of the day's puzzle.
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 7;
+
+    type Input<'a> = Vec<(Hand, usize)>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        Ok(input
+            .lines()
+            .map(|line| parse_bid(input, line))
+            .collect::<Result<_, _>>()?)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer> {
+        let bids = input
+            .iter()
+            .map(|&(hand, bid)| (hand.with_jacks(), bid))
+            .collect();
+        total_winnings(bids, arith::policy()).map(Answer::from)
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer> {
+        total_winnings(input.clone(), arith::policy()).map(Answer::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{input::fixture, solution::answer, Error, Part};
+
+    use super::*;
+
+    fn example() -> String {
+        fixture(env!("CARGO_MANIFEST_DIR"), "example.txt")
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(
+            answer::<Day>(&example(), Part::One),
+            Ok(Answer::Integer(6440))
+        );
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(
+            answer::<Day>(&example(), Part::Two),
+            Ok(Answer::Integer(5905))
+        );
+    }
+
+    #[test]
+    fn hand_types() {
+        let input = example();
+        let bids = Day::parse(&input).unwrap();
+        let types = |hands: Vec<Hand>| hands.iter().map(Hand::typ).collect::<Vec<_>>();
+        assert_eq!(
+            types(bids.iter().map(|(hand, _)| hand.with_jacks()).collect()),
+            [
+                Type::OnePair,
+                Type::ThreeOfAKind,
+                Type::TwoPair,
+                Type::TwoPair,
+                Type::ThreeOfAKind
+            ]
+        );
+        assert_eq!(
+            types(bids.iter().map(|(hand, _)| *hand).collect()),
+            [
+                Type::OnePair,
+                Type::FourOfAKind,
+                Type::TwoPair,
+                Type::FourOfAKind,
+                Type::FourOfAKind
+            ]
+        );
+    }
+
+    #[test]
+    fn overflowing_winnings() {
+        let max = i128::from(usize::MAX as u64);
+        let bids = vec![(Hand([2; 5]), 1), (Hand([3; 5]), usize::MAX)];
+        let overflow = |step: &str| Err(Error::Overflow(step.to_string()));
+        assert_eq!(
+            total_winnings(bids.clone(), Policy::Checked),
+            overflow("winnings")
+        );
+        assert_eq!(total_winnings(bids.clone(), Policy::Saturating), Ok(max));
+        assert_eq!(total_winnings(bids, Policy::Wide), Ok(2 * max + 1));
+
+        let bids = vec![(Hand([2; 5]), usize::MAX), (Hand([3; 5]), 1)];
+        assert_eq!(
+            total_winnings(bids, Policy::Checked),
+            overflow("total winnings")
+        );
+    }
+
+    #[test]
+    fn trailing_tokens_are_rejected() {
+        let Err(Error::Parse(error)) = Day::parse("32T3K 765 junk\n") else {
+            panic!("trailing token parsed");
+        };
+        assert_eq!((error.line, error.column), (1, 11));
+        assert_eq!(error.expected, "end of line");
+    }
+}
@@ -0,0 +1,214 @@
+//! 2023 day 8: Haunted Wasteland.
+
+#![warn(missing_docs)]
+
+use std::collections::HashMap;
+
+use aoc_common::{
+    arith::{self, Policy},
+    cycle::{self, Mode},
+    parse, Answer, Error, ParseError, Result, Solution,
+};
+
+/// Which child of a node to go to.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Direction {
+    /// The first child, `L`.
+    Left = 0,
+    /// The second child, `R`.
+    Right = 1,
+}
+
+/// The three-character name of a node.
+pub type Node<'a> = &'a str;
+
+/// The left and right child of every node.
+pub type Map<'a> = HashMap<Node<'a>, [Node<'a>; 2]>;
+
+/// The directions to follow, repeated forever.
+pub type Instruction = Vec<Direction>;
+
+/// The left/right instruction and the node map.
+pub struct Network<'a> {
+    /// The directions to follow.
+    pub instruction: Instruction,
+    /// The children of every node.
+    pub map: Map<'a>,
+}
+
+/// Parses the instruction line and, after an empty line, the node lines.
+pub fn parse_input(input: &str) -> Result<Network<'_>, ParseError> {
+    let [instruction_str, map_str] = parse::sections(input, input)?;
+    let instruction_str = instruction_str.trim();
+    let instruction = instruction_str
+        .char_indices()
+        .map(|(index, c)| match c {
+            'L' => Ok(Direction::Left),
+            'R' => Ok(Direction::Right),
+            _ => Err(ParseError::at(
+                input,
+                &instruction_str[index..],
+                "'L' or 'R'",
+            )),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if instruction.is_empty() {
+        return Err(ParseError::at(input, instruction_str, "'L' or 'R'"));
+    }
+    let nodes = map_str
+        .lines()
+        .map(|line| {
+            let (node, children) = parse::split_once(input, line, " = ")?;
+            let children = parse::delimited(input, children.trim(), "(", ")")?;
+            let children = parse::separated_n(input, children, ", ", |child| Ok(child.trim()))?;
+            Ok((node.trim(), children))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let map = nodes.iter().copied().collect::<Map>();
+    let mut children = nodes.iter().flat_map(|(_, children)| children);
+    if let Some(child) = children.find(|child| !map.contains_key(*child)) {
+        return Err(ParseError::at(input, child, "a node defined in the map"));
+    }
+    Ok(Network { instruction, map })
+}
+
+/// How many steps it takes from `start` to first reach `end`, or `None` if the walk loops without
+/// reaching it.
+pub fn num_steps<'a>(
+    instruction: &Instruction,
+    map: &Map<'a>,
+    start: Node<'a>,
+    end: Node<'a>,
+) -> Option<usize> {
+    // The walk is a sequence of nodes and positions in the instruction, which must repeat.
+    let step = |&(node, index): &(Node<'a>, usize)| {
+        (
+            map[node][instruction[index] as usize],
+            (index + 1) % instruction.len(),
+        )
+    };
+    let walk_start = (start, 0);
+    let cycle = cycle::find(&walk_start, step, Mode::Brent);
+    std::iter::successors(Some(walk_start), |state| Some(step(state)))
+        .take(cycle.start + cycle.length + 1)
+        .skip(1)
+        .position(|(node, _)| node == end)
+        .map(|index| index + 1)
+}
+
+/// The solution of the day's puzzle.
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 8;
+
+    type Input<'a> = Network<'a>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer> {
+        if !input.map.contains_key("AAA") {
+            return Err(Error::NoSolution("there is no AAA".to_string()));
+        }
+        num_steps(&input.instruction, &input.map, "AAA", "ZZZ")
+            .map(Answer::from)
+            .ok_or_else(|| Error::NoSolution("ZZZ is unreachable from AAA".to_string()))
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer> {
+        ghost_steps(&input.instruction, &input.map, arith::policy()).map(Answer::from)
+    }
+}
+
+/// How many steps it takes until every walk from a node ending in `A` is on a node ending in `Z`
+/// at once.
+pub fn ghost_steps(instruction: &Instruction, map: &Map, policy: Policy) -> Result<i128> {
+    let starts = map
+        .keys()
+        .filter(|node| node.ends_with("A"))
+        .collect::<Vec<_>>();
+    let ends = map
+        .keys()
+        .filter(|node| node.ends_with("Z"))
+        .collect::<Vec<_>>();
+    let possible_steps = starts
+        .iter()
+        .map(|start| {
+            ends.iter()
+                .flat_map(|end| num_steps(instruction, map, start, end))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    // Interesting...
+    if starts.is_empty() || possible_steps.iter().any(|steps| steps.len() != 1) {
+        let reason = "not every walk from a node ending in A reaches exactly one node ending in Z";
+        return Err(Error::NoSolution(reason.to_string()));
+    }
+    let steps = possible_steps.into_iter().flat_map(IntoIterator::into_iter);
+    lcm(steps.map(|steps| steps as i128), policy)
+}
+
+/// The least common multiple of `steps`, fitted into `usize`.
+pub fn lcm(steps: impl IntoIterator<Item = i128>, policy: Policy) -> Result<i128> {
+    steps.into_iter().try_fold(1, |lcm, steps| {
+        let gcd = num::integer::gcd(lcm, steps);
+        policy.mul::<usize>(lcm / gcd, steps, "least common multiple of steps")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{input::fixture, solution::answer, Part};
+
+    use super::*;
+
+    fn example_1() -> String {
+        fixture(env!("CARGO_MANIFEST_DIR"), "example_1.txt")
+    }
+
+    fn example_2() -> String {
+        fixture(env!("CARGO_MANIFEST_DIR"), "example_2.txt")
+    }
+
+    fn example_3() -> String {
+        fixture(env!("CARGO_MANIFEST_DIR"), "example_3.txt")
+    }
+
+    #[test]
+    fn part1_example_1() {
+        assert_eq!(
+            answer::<Day>(&example_1(), Part::One),
+            Ok(Answer::Integer(2))
+        );
+    }
+
+    #[test]
+    fn part1_example_2() {
+        assert_eq!(
+            answer::<Day>(&example_2(), Part::One),
+            Ok(Answer::Integer(6))
+        );
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(
+            answer::<Day>(&example_3(), Part::Two),
+            Ok(Answer::Integer(6))
+        );
+    }
+
+    #[test]
+    fn overflowing_lcm() {
+        let prime = (1 << 61) - 1;
+        assert_eq!(lcm([4, 6, 10], Policy::Checked), Ok(60));
+        let overflow = Error::Overflow("least common multiple of steps".to_string());
+        assert_eq!(lcm([prime, 16], Policy::Checked), Err(overflow));
+        let max = i128::from(usize::MAX as u64);
+        assert_eq!(lcm([prime, 16], Policy::Saturating), Ok(max));
+        assert_eq!(lcm([prime, 16], Policy::Wide), Ok(prime * 16));
+    }
+}
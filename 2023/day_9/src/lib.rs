@@ -0,0 +1,133 @@
+//! 2023 day 9: Mirage Maintenance.
+
+#![warn(missing_docs)]
+
+use aoc_common::{
+    arith::{self, Policy},
+    parse, Answer, ParseError, Result, Solution,
+};
+
+/// Extrapolates the value before the first one, with every difference an `i32` under `policy`.
+pub fn extrapolate(sequence: Vec<i32>, policy: Policy) -> Result<i128> {
+    let mut diffs = vec![sequence.into_iter().map(i128::from).collect::<Vec<_>>()];
+    loop {
+        let seq = diffs.last().unwrap();
+        let diff = seq
+            .iter()
+            .skip(1)
+            .zip(seq.iter())
+            .map(|(&a, &b)| policy.sub::<i32>(a, b, "differences"))
+            .collect::<Result<Vec<_>>>()?;
+        if diff.iter().all(|d| *d == 0) {
+            let terms = diffs
+                .iter()
+                .enumerate()
+                .map(|(index, seq)| if index % 2 == 0 { seq[0] } else { -seq[0] });
+            return policy.sum::<i32>(terms, "extrapolation");
+        }
+        diffs.push(diff);
+    }
+}
+
+/// The solution of the day's puzzle.
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 9;
+
+    type Input<'a> = Vec<Vec<i32>>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        Ok(input
+            .lines()
+            .map(|line| {
+                let sequence = line
+                    .split_whitespace()
+                    .map(|s| parse::number(input, s))
+                    .collect::<Result<Vec<_>, _>>()?;
+                if sequence.is_empty() {
+                    return Err(ParseError::at(input, line, "a number"));
+                }
+                Ok(sequence)
+            })
+            .collect::<Result<_, _>>()?)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer> {
+        // Extrapolating before the reversed sequence extrapolates after the original one.
+        let policy = arith::policy();
+        let mut result = 0;
+        for sequence in input {
+            let value = extrapolate(sequence.iter().rev().copied().collect(), policy)?;
+            result = policy.add::<i32>(result, value, "sum of extrapolations")?;
+        }
+        Ok(result.into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer> {
+        let policy = arith::policy();
+        let mut result = 0;
+        for sequence in input {
+            let value = extrapolate(sequence.clone(), policy)?;
+            result = policy.add::<i32>(result, value, "sum of extrapolations")?;
+        }
+        Ok(result.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{input::fixture, solution::answer, Error, Part};
+
+    use super::*;
+
+    fn example() -> String {
+        fixture(env!("CARGO_MANIFEST_DIR"), "example.txt")
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(
+            answer::<Day>(&example(), Part::One),
+            Ok(Answer::Integer(114))
+        );
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(answer::<Day>(&example(), Part::Two), Ok(Answer::Integer(2)));
+    }
+
+    #[test]
+    fn extrapolate_backwards() {
+        assert_eq!(
+            extrapolate(vec![10, 13, 16, 21, 30, 45], Policy::Checked),
+            Ok(5)
+        );
+        assert_eq!(
+            extrapolate(vec![45, 30, 21, 16, 13, 10], Policy::Checked),
+            Ok(68)
+        );
+    }
+
+    #[test]
+    fn empty_lines_are_rejected() {
+        for (input, line) in [("\n", 1), ("1 2 3\n\n4 5\n", 2), ("1\n  \n", 2)] {
+            let Err(Error::Parse(error)) = Day::parse(input) else {
+                panic!("{:?} parsed", input);
+            };
+            assert_eq!(error.line, line, "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn overflowing_differences() {
+        let sequence = vec![i32::MIN, i32::MAX, i32::MIN];
+        assert_eq!(
+            extrapolate(sequence.clone(), Policy::Checked),
+            Err(Error::Overflow("differences".to_string()))
+        );
+        assert_eq!(extrapolate(sequence, Policy::Wide), Ok(-7 * (1 << 31) + 3));
+    }
+}
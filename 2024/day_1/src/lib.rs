@@ -0,0 +1,137 @@
+//! 2024 day 1: Historian Hysteria.
+
+#![warn(missing_docs)]
+
+use std::collections::HashMap;
+
+use aoc_common::{
+    arith::{self, Policy},
+    parse, Answer, ParseError, Result, Solution,
+};
+
+/// The solution of the day's puzzle.
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 1;
+
+    type Input<'a> = Vec<(i32, i32)>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        Ok(input
+            .lines()
+            .map(|line| {
+                let mut words = line.split_whitespace();
+                let first = parse::number(input, parse::next(input, line, &mut words, "number")?)?;
+                let second = parse::number(input, parse::next(input, line, &mut words, "number")?)?;
+                if let Some(extra) = words.next() {
+                    return Err(ParseError::at(input, extra, "end of line"));
+                }
+                Ok((first, second))
+            })
+            .collect::<Result<_, ParseError>>()?)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer> {
+        total_distance(input, arith::policy()).map(Answer::from)
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer> {
+        similarity_score(input, arith::policy()).map(Answer::from)
+    }
+}
+
+/// Sums the distances between the smallest numbers of both lists, then the second smallest, and so
+/// on, with every distance an `i32` under `policy`.
+pub fn total_distance(pairs: &[(i32, i32)], policy: Policy) -> Result<i128> {
+    let (mut first, mut second) = pairs.iter().copied().unzip::<_, _, Vec<_>, Vec<_>>();
+    first.sort_unstable();
+    second.sort_unstable();
+    let distances = first
+        .iter()
+        .zip(second.iter())
+        .map(|(&a, &b)| {
+            let (a, b) = (i128::from(a), i128::from(b));
+            policy.sub::<i32>(a.max(b), a.min(b), "distance")
+        })
+        .collect::<Result<Vec<_>>>()?;
+    policy.sum::<i32>(distances, "total distance")
+}
+
+/// Sums every number of the left list times how often it appears in the right list, with every
+/// product an `i32` under `policy`.
+pub fn similarity_score(pairs: &[(i32, i32)], policy: Policy) -> Result<i128> {
+    let mut first = vec![];
+    let mut second = HashMap::new();
+    for &(n1, n2) in pairs {
+        first.push(n1);
+        *second.entry(n2).or_insert(0) += 1;
+    }
+    let mut result = 0;
+    for first in first {
+        let count = second.get(&first).copied().unwrap_or(0);
+        let similarity = policy.mul::<i32>(first.into(), count, "similarity")?;
+        result = policy.add::<i32>(result, similarity, "similarity score")?;
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{input::fixture, solution::answer, Error, Part};
+
+    use super::*;
+
+    fn example() -> String {
+        fixture(env!("CARGO_MANIFEST_DIR"), "example.txt")
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(
+            answer::<Day>(&example(), Part::One),
+            Ok(Answer::Integer(11))
+        );
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(
+            answer::<Day>(&example(), Part::Two),
+            Ok(Answer::Integer(31))
+        );
+    }
+
+    #[test]
+    fn overflowing_answers() {
+        let pairs = Day::parse("2147483647 -2147483648\n").unwrap();
+        assert_eq!(
+            total_distance(&pairs, Policy::Checked),
+            Err(Error::Overflow("distance".to_string()))
+        );
+        assert_eq!(
+            total_distance(&pairs, Policy::Saturating),
+            Ok(i32::MAX.into())
+        );
+        assert_eq!(total_distance(&pairs, Policy::Wide), Ok(4_294_967_295));
+        let pairs = Day::parse("2147483647 2147483647\n2147483647 2147483647\n").unwrap();
+        assert_eq!(
+            similarity_score(&pairs, Policy::Checked),
+            Err(Error::Overflow("similarity".to_string()))
+        );
+        assert_eq!(
+            similarity_score(&pairs, Policy::Wide),
+            Ok(4 * i128::from(i32::MAX))
+        );
+    }
+
+    #[test]
+    fn trailing_tokens_are_rejected() {
+        let Err(Error::Parse(error)) = Day::parse("3   4\n4   3 2\n") else {
+            panic!("trailing token parsed");
+        };
+        assert_eq!((error.line, error.column), (2, 7));
+        assert_eq!(error.expected, "end of line");
+    }
+}
@@ -0,0 +1,113 @@
+//! 2024 day 2: Red-Nosed Reports.
+
+#![warn(missing_docs)]
+
+use std::cmp::Ordering;
+
+use aoc_common::{parse, Answer, Result, Solution};
+
+/// Which way the levels must go.
+#[derive(Debug, PartialEq)]
+pub enum Order {
+    /// Every level above the one before.
+    Ascending,
+    /// Every level below the one before.
+    Descending,
+    /// Either way, as set by the first two levels.
+    Any,
+}
+
+/// Whether the levels keep going in `order`, changing by 1 to 3 at each step.
+pub fn is_safe(numbers: &[u32], order: Order) -> bool {
+    if numbers.len() < 2 {
+        return true;
+    }
+    match numbers[0].cmp(&numbers[1]) {
+        Ordering::Less => {
+            if order == Order::Descending {
+                return false;
+            }
+            if numbers[1] - numbers[0] > 3 {
+                return false;
+            }
+            is_safe(&numbers[1..], Order::Ascending)
+        }
+        Ordering::Greater => {
+            if order == Order::Ascending {
+                return false;
+            }
+            if numbers[0] - numbers[1] > 3 {
+                return false;
+            }
+            is_safe(&numbers[1..], Order::Descending)
+        }
+        Ordering::Equal => false,
+    }
+}
+
+/// The solution of the day's puzzle.
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 2;
+
+    type Input<'a> = Vec<Vec<u32>>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        Ok(input
+            .lines()
+            .map(|line| {
+                line.split_whitespace()
+                    .map(|n| parse::number(input, n))
+                    .collect()
+            })
+            .collect::<Result<_, _>>()?)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer> {
+        let result = input
+            .iter()
+            .filter(|numbers| is_safe(numbers, Order::Any))
+            .count();
+        Ok(result.into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer> {
+        let result = input
+            .iter()
+            .filter(|numbers| is_safe_with_dampener(numbers))
+            .count();
+        Ok(result.into())
+    }
+}
+
+/// Whether the levels are safe once at most one of them is removed.
+pub fn is_safe_with_dampener(numbers: &[u32]) -> bool {
+    (0..numbers.len()).any(|i| {
+        let mut numbers = numbers.to_vec();
+        numbers.remove(i);
+        is_safe(&numbers, Order::Any)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{input::fixture, solution::answer, Part};
+
+    use super::*;
+
+    fn example() -> String {
+        fixture(env!("CARGO_MANIFEST_DIR"), "example.txt")
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(answer::<Day>(&example(), Part::One), Ok(Answer::Integer(2)));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(answer::<Day>(&example(), Part::Two), Ok(Answer::Integer(4)));
+    }
+}
@@ -0,0 +1,139 @@
+//! 2024 day 3: Mull It Over.
+
+#![warn(missing_docs)]
+
+use aoc_common::{
+    arith::{self, Policy},
+    parse, Answer, ParseError, Result, Solution,
+};
+use regex::Regex;
+
+/// A valid instruction found in the corrupted memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    /// `do()`, enabling the instructions after it.
+    Do,
+    /// `don't()`, disabling the instructions after it.
+    Dont,
+    /// `mul(a,b)`, multiplying its operands.
+    Mul(i32, i32),
+}
+
+/// The solution of the day's puzzle.
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 3;
+
+    type Input<'a> = Vec<Instruction>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        let regex = Regex::new(r"don't|do|mul\((\d+),(\d+)\)").unwrap();
+        Ok(regex
+            .captures_iter(input)
+            .map(|cap| {
+                if &cap[0] == "don't" {
+                    Ok(Instruction::Dont)
+                } else if &cap[0] == "do" {
+                    Ok(Instruction::Do)
+                } else {
+                    let a = parse::number(input, cap.get(1).unwrap().as_str())?;
+                    let b = parse::number(input, cap.get(2).unwrap().as_str())?;
+                    Ok(Instruction::Mul(a, b))
+                }
+            })
+            .collect::<Result<_, ParseError>>()?)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer> {
+        Ok(sum_of_products(input, arith::policy())?.into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer> {
+        Ok(sum_of_enabled_products(input, arith::policy())?.into())
+    }
+}
+
+/// The product of a `mul` instruction, as an `i32` under `policy`.
+fn product(a: i32, b: i32, policy: Policy) -> Result<i128> {
+    policy.mul::<i32>(a.into(), b.into(), "mul")
+}
+
+/// Sums the products of every `mul` instruction, as `i32`s under `policy`.
+pub fn sum_of_products(instructions: &[Instruction], policy: Policy) -> Result<i128> {
+    let products = instructions
+        .iter()
+        .map(|instruction| match *instruction {
+            Instruction::Mul(a, b) => product(a, b, policy),
+            _ => Ok(0),
+        })
+        .collect::<Result<Vec<_>>>()?;
+    policy.sum::<i32>(products, "sum of products")
+}
+
+/// Sums the products of the `mul` instructions not disabled by a `don't()`.
+pub fn sum_of_enabled_products(instructions: &[Instruction], policy: Policy) -> Result<i128> {
+    let mut state = (0, true);
+    for instruction in instructions {
+        match *instruction {
+            Instruction::Dont => state.1 = false,
+            Instruction::Do => state.1 = true,
+            Instruction::Mul(a, b) => {
+                if state.1 {
+                    let product = product(a, b, policy)?;
+                    state.0 = policy.add::<i32>(state.0, product, "sum of products")?;
+                }
+            }
+        }
+    }
+    Ok(state.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{input::fixture, solution::answer, Error, Part};
+
+    use super::*;
+
+    fn example_1() -> String {
+        fixture(env!("CARGO_MANIFEST_DIR"), "example_1.txt")
+    }
+
+    fn example_2() -> String {
+        fixture(env!("CARGO_MANIFEST_DIR"), "example_2.txt")
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(
+            answer::<Day>(&example_1(), Part::One),
+            Ok(Answer::Integer(161))
+        );
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(
+            answer::<Day>(&example_2(), Part::Two),
+            Ok(Answer::Integer(48))
+        );
+    }
+
+    #[test]
+    fn overflowing_products() {
+        let instructions = Day::parse("mul(50000,50000)mul(1,2)").unwrap();
+        assert_eq!(
+            sum_of_products(&instructions, Policy::Checked),
+            Err(Error::Overflow("mul".to_string()))
+        );
+        assert_eq!(
+            sum_of_products(&instructions, Policy::Saturating),
+            Ok(i32::MAX.into())
+        );
+        assert_eq!(
+            sum_of_enabled_products(&instructions, Policy::Wide),
+            Ok(2_500_000_002)
+        );
+    }
+}
@@ -0,0 +1,168 @@
+use std::{
+    fmt::{Display, Formatter},
+    panic,
+    time::Duration,
+};
+
+use aoc_common::{solution::Solver, Part};
+
+/// A timed step of solving a day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stage {
+    Parse,
+    Part(Part),
+}
+
+impl Display for Stage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Stage::Parse => write!(f, "parse"),
+            Stage::Part(part) => write!(f, "part{}", part),
+        }
+    }
+}
+
+/// Summary of the durations of one stage over all iterations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    /// Panics if `samples` is empty.
+    pub fn of(mut samples: Vec<Duration>) -> Stats {
+        samples.sort();
+        Stats {
+            min: samples[0],
+            median: samples[samples.len() / 2],
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+/// The timing of one stage of one day.
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub year: u16,
+    pub day: u8,
+    pub stage: Stage,
+    pub iterations: usize,
+    pub stats: Stats,
+}
+
+/// Solves `parts` of `input` `iterations` times, which must be at least 1, and summarizes how long
+/// parsing and each part took. Fails if the solver fails or panics in any iteration.
+pub fn bench(
+    solver: &dyn Solver,
+    input: &str,
+    parts: &[Part],
+    iterations: usize,
+) -> Result<Vec<Report>, String> {
+    let mut parse = Vec::with_capacity(iterations);
+    let mut per_part = vec![Vec::with_capacity(iterations); parts.len()];
+    for _ in 0..iterations {
+        let solved =
+            panic::catch_unwind(panic::AssertUnwindSafe(|| solver.solve_timed(input, parts)));
+        let (answers, timings) = match solved {
+            Ok(result) => result.map_err(|error| error.to_string())?,
+            Err(_) => return Err("solver panicked".to_string()),
+        };
+        for (part, answer) in parts.iter().zip(answers) {
+            answer.map_err(|error| format!("part {}: {}", part, error))?;
+        }
+        parse.push(timings.parse);
+        for (samples, duration) in per_part.iter_mut().zip(timings.parts) {
+            samples.push(duration);
+        }
+    }
+    let stages = std::iter::once((Stage::Parse, parse))
+        .chain(parts.iter().map(|&part| Stage::Part(part)).zip(per_part));
+    Ok(stages
+        .map(|(stage, samples)| Report {
+            year: solver.year(),
+            day: solver.day(),
+            stage,
+            iterations,
+            stats: Stats::of(samples),
+        })
+        .collect())
+}
+
+pub fn print_table(reports: &[Report]) {
+    println!(
+        "{:<4}  {:>3}  {:<5}  {:>10}  {:>10}  {:>10}  {:>10}",
+        "year", "day", "stage", "iterations", "min", "median", "max"
+    );
+    for report in reports {
+        println!(
+            "{:<4}  {:>3}  {:<5}  {:>10}  {:>10.2?}  {:>10.2?}  {:>10.2?}",
+            report.year,
+            report.day,
+            report.stage,
+            report.iterations,
+            report.stats.min,
+            report.stats.median,
+            report.stats.max
+        );
+    }
+}
+
+/// Prints the reports as a JSON array with one object per stage, durations in nanoseconds.
+pub fn print_json(reports: &[Report]) {
+    let objects = reports
+        .iter()
+        .map(|report| {
+            format!(
+                "  {{\"year\": {}, \"day\": {}, \"stage\": \"{}\", \"iterations\": {}, \
+                 \"min_ns\": {}, \"median_ns\": {}, \"max_ns\": {}}}",
+                report.year,
+                report.day,
+                report.stage,
+                report.iterations,
+                report.stats.min.as_nanos(),
+                report.stats.median.as_nanos(),
+                report.stats.max.as_nanos()
+            )
+        })
+        .collect::<Vec<_>>();
+    if objects.is_empty() {
+        println!("[]");
+    } else {
+        println!("[\n{}\n]", objects.join(",\n"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_of_samples() {
+        let samples = [5, 1, 4, 2, 3].map(Duration::from_millis).to_vec();
+        assert_eq!(
+            Stats::of(samples),
+            Stats {
+                min: Duration::from_millis(1),
+                median: Duration::from_millis(3),
+                max: Duration::from_millis(5),
+            }
+        );
+    }
+
+    #[test]
+    fn bench_every_stage() {
+        let solver = &aoc_common::solution::Day::<aoc_2023_day_15::Day>::SOLVER;
+        let reports = bench(solver, "rn=1,cm-", &Part::ALL, 3).unwrap();
+        let stages = reports
+            .iter()
+            .map(|report| report.stage)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            stages,
+            [Stage::Parse, Stage::Part(Part::One), Stage::Part(Part::Two)]
+        );
+        assert!(reports.iter().all(|report| report.iterations == 3));
+    }
+}
@@ -0,0 +1,437 @@
+mod bench;
+mod scaffold;
+mod solvers;
+mod verify;
+
+use std::{panic, path::PathBuf, process::ExitCode};
+
+use aoc_common::{
+    arith::{self, Policy},
+    batch,
+    input::{self, Source},
+    output, parallel,
+    provider::Provider,
+    render::Format,
+    solution::Solver,
+    Answer, Part,
+};
+use solvers::SOLVERS;
+
+const USAGE: &str =
+    "Usage: aoc [verify|bench|batch] [--year <YEAR>] [--day <DAY>] [--part <PART>] [--input <PATH|->]
+  [--arith <checked|saturating|wide>] [--threads <N>]
+  run: [--render <ansi|svg>] [--json]
+  verify: [--answers <PATH>]
+  bench: [--iterations <N>] [--json]
+  batch: --input <DIR|PATH|-> [--json]
+  new <YEAR> <DAY>: creates the crate of a new day
+Without --input, a day reads its src/input.txt, or else its input cached under $AOC_CACHE (inputs/
+by default), which is fetched from $AOC_ENDPOINT with the session token $AOC_SESSION if missing.";
+
+#[derive(Debug, Default, PartialEq, Eq)]
+enum Command {
+    /// Print the answers.
+    #[default]
+    Run,
+    /// Check the answers against the registry.
+    Verify,
+    /// Time parsing and each part.
+    Bench,
+    /// Answer every input in a directory.
+    Batch,
+    /// Create the crate of a new day.
+    New,
+}
+
+#[derive(Debug, Default)]
+struct Args {
+    command: Command,
+    year: Option<u16>,
+    day: Option<u8>,
+    part: Option<Part>,
+    input: Option<String>,
+    answers: Option<String>,
+    iterations: Option<usize>,
+    json: bool,
+    render: Option<Format>,
+    /// What to do when an answer overflows the integer type a day uses for it.
+    arith: Policy,
+    /// Threads for the days' parallel loops with the `parallel` feature; 1 takes the serial path.
+    threads: Option<usize>,
+}
+
+fn parse_args(args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut result = Args::default();
+    let mut args = args.peekable();
+    let command = match args.peek().map(String::as_str) {
+        Some("verify") => Some(Command::Verify),
+        Some("bench") => Some(Command::Bench),
+        Some("batch") => Some(Command::Batch),
+        Some("new") => Some(Command::New),
+        _ => None,
+    };
+    if let Some(command) = command {
+        args.next();
+        result.command = command;
+    }
+    if result.command == Command::New {
+        let mut positional = || {
+            args.next()
+                .ok_or_else(|| format!("new requires a year and a day\n{}", USAGE))
+        };
+        result.year = Some(parse_number(&positional()?)?);
+        let day = parse_number(&positional()?)?;
+        if !(1..=25).contains(&day) {
+            return Err(format!("Invalid day: {}", day));
+        }
+        result.day = Some(day);
+    }
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("Missing value for {}", arg))
+        };
+        match arg.as_str() {
+            "--year" => result.year = Some(parse_number(&value()?)?),
+            "--day" => result.day = Some(parse_number(&value()?)?),
+            "--part" => {
+                let part = value()?;
+                result.part = Some(
+                    Part::from_number(parse_number(&part)?)
+                        .ok_or_else(|| format!("Invalid part: {}", part))?,
+                );
+            }
+            "--input" => result.input = Some(value()?),
+            "--answers" => result.answers = Some(value()?),
+            "--iterations" => {
+                let iterations = parse_number(&value()?)?;
+                if iterations == 0 {
+                    return Err("--iterations must be at least 1".to_string());
+                }
+                result.iterations = Some(iterations);
+            }
+            "--json" => result.json = true,
+            "--render" => {
+                let format = value()?;
+                result.render = Some(
+                    Format::from_name(&format)
+                        .ok_or_else(|| format!("Invalid render format: {}", format))?,
+                );
+            }
+            "--threads" => {
+                let threads = parse_number(&value()?)?;
+                if threads == 0 {
+                    return Err("--threads must be at least 1".to_string());
+                }
+                result.threads = Some(threads);
+            }
+            "--arith" => {
+                let policy = value()?;
+                result.arith = Policy::from_name(&policy)
+                    .ok_or_else(|| format!("Invalid arithmetic policy: {}", policy))?;
+            }
+            "--help" | "-h" => return Err(USAGE.to_string()),
+            other => return Err(format!("Unknown argument: {}\n{}", other, USAGE)),
+        }
+    }
+    Ok(result)
+}
+
+fn parse_number<T: std::str::FromStr>(value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("Invalid number: {}", value))
+}
+
+/// Directory containing the `<year>/day_<day>` crates.
+fn root_dir() -> PathBuf {
+    std::env::var_os("AOC_ROOT")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/..")))
+}
+
+/// The input at `path`. Without a path, the day's `input.txt` if there is one, or else its cached
+/// input, which is fetched first if needed.
+fn input_source(path: Option<&str>, year: u16, day: u8) -> Result<Source, String> {
+    if let Some(path) = path {
+        return Ok(Source::from_arg(path));
+    }
+    let day_input = input::default_path(
+        root_dir()
+            .join(year.to_string())
+            .join(format!("day_{}", day)),
+    );
+    if day_input.is_file() {
+        return Ok(Source::File(day_input));
+    }
+    Provider::from_env(root_dir().join("inputs"))
+        .fetch(year, day)
+        .map(Source::File)
+        .map_err(|error| format!("Failed to provide the input: {}", error))
+}
+
+fn read_input(path: Option<&str>, year: u16, day: u8) -> Result<String, String> {
+    input_source(path, year, day)?
+        .read()
+        .map_err(|error| format!("Failed to read {}", error))
+}
+
+/// Reads the input of one day and answers `parts` of it, turning a failure to read, parse or
+/// even a panic into a message.
+fn solve(
+    solver: &dyn Solver,
+    path: Option<&str>,
+    parts: &[Part],
+) -> Result<Vec<aoc_common::Result<Answer>>, String> {
+    let input = read_input(path, solver.year(), solver.day())?;
+    match panic::catch_unwind(panic::AssertUnwindSafe(|| solver.solve(&input, parts))) {
+        Ok(result) => result.map_err(|error| error.to_string()),
+        Err(_) => Err("solver panicked".to_string()),
+    }
+}
+
+/// Answers `parts` of one day and prints the answers. Returns whether every part succeeded.
+fn run(solver: &dyn Solver, path: Option<&str>, parts: &[Part]) -> bool {
+    let (year, day) = (solver.year(), solver.day());
+    let answers = match solve(solver, path, parts) {
+        Ok(answers) => answers,
+        Err(message) => {
+            eprintln!("{} day {}: {}", year, day, message);
+            return false;
+        }
+    };
+    let mut succeeded = true;
+    for (part, answer) in parts.iter().zip(answers) {
+        match answer {
+            Ok(answer) => println!("{} day {} part {}: {}", year, day, part, answer),
+            Err(error) => {
+                eprintln!("{} day {} part {}: {}", year, day, part, error);
+                succeeded = false;
+            }
+        }
+    }
+    succeeded
+}
+
+/// Renders `parts` of one day in `format` instead of answering them. Returns whether every part
+/// could be rendered.
+fn render(solver: &dyn Solver, path: Option<&str>, parts: &[Part], format: Format) -> bool {
+    let (year, day) = (solver.year(), solver.day());
+    let input = match read_input(path, year, day) {
+        Ok(input) => input,
+        Err(message) => {
+            eprintln!("{} day {}: {}", year, day, message);
+            return false;
+        }
+    };
+    let mut succeeded = true;
+    for &part in parts {
+        let rendered = panic::catch_unwind(panic::AssertUnwindSafe(|| solver.render(&input, part)));
+        let message = match rendered {
+            Ok(Ok(Some(picture))) => {
+                print!("{}", picture.render(format));
+                continue;
+            }
+            Ok(Ok(None)) => "can't be rendered".to_string(),
+            Ok(Err(error)) => error.to_string(),
+            Err(_) => "solver panicked".to_string(),
+        };
+        eprintln!("{} day {} part {}: {}", year, day, part, message);
+        succeeded = false;
+    }
+    succeeded
+}
+
+/// Checks every registered answer of `solvers`. Returns whether all of them are right.
+fn verify_all(args: &Args, solvers: &[&dyn Solver], parts: &[Part]) -> bool {
+    let path = args
+        .answers
+        .as_ref()
+        .map_or_else(|| root_dir().join("answers.txt"), PathBuf::from);
+    let registry = match Source::File(path).read() {
+        Ok(registry) => registry,
+        Err(error) => {
+            eprintln!("Failed to read {}; nothing was checked", error);
+            return false;
+        }
+    };
+    let registry = match verify::parse_registry(&registry) {
+        Ok(registry) => registry,
+        Err(error) => {
+            eprintln!("Invalid answer registry at {}", error);
+            return false;
+        }
+    };
+    let registry = registry
+        .into_iter()
+        .filter(|entry| parts.contains(&entry.part))
+        .collect::<Vec<_>>();
+    verify::verify(solvers, &registry, |solver, parts| {
+        solve(solver, args.input.as_deref(), parts)
+    })
+}
+
+/// Times `parts` of every day of `solvers` and prints a report. Returns whether every day could be
+/// timed.
+fn bench_all(args: &Args, solvers: &[&dyn Solver], parts: &[Part]) -> bool {
+    let iterations = args.iterations.unwrap_or(10);
+    let mut reports = Vec::new();
+    let mut succeeded = true;
+    for &solver in solvers {
+        let (year, day) = (solver.year(), solver.day());
+        let result = read_input(args.input.as_deref(), year, day)
+            .and_then(|input| bench::bench(solver, &input, parts, iterations));
+        match result {
+            Ok(day_reports) => reports.extend(day_reports),
+            Err(message) => {
+                eprintln!("{} day {}: {}", year, day, message);
+                succeeded = false;
+            }
+        }
+    }
+    if args.json {
+        bench::print_json(&reports);
+    } else {
+        bench::print_table(&reports);
+    }
+    succeeded
+}
+
+/// Answers `parts` of every input named by `path` and prints a table of them, or JSON records.
+/// Returns whether all of them succeeded.
+fn batch_all(solver: &dyn Solver, path: &str, parts: &[Part], json: bool) -> bool {
+    let sources = match batch::sources(path) {
+        Ok(sources) => sources,
+        Err(error) => {
+            eprintln!("Failed to list {}: {}", path, error);
+            return false;
+        }
+    };
+    let rows = batch::run(solver, &sources, parts);
+    if json {
+        let records = rows
+            .iter()
+            .flat_map(|row| row.records(solver, parts))
+            .collect::<Vec<_>>();
+        print!("{}", output::to_json(&records));
+    } else {
+        print!("{}", batch::table(&rows, parts));
+    }
+    rows.iter().all(batch::Row::succeeded)
+}
+
+/// Answers `parts` of every day of `solvers` and prints them all as JSON records. Returns whether
+/// every part succeeded.
+fn run_json(solvers: &[&dyn Solver], path: Option<&str>, parts: &[Part]) -> bool {
+    let mut records = vec![];
+    let mut succeeded = true;
+    for &solver in solvers {
+        let source = match input_source(path, solver.year(), solver.day()) {
+            Ok(source) => source,
+            Err(message) => {
+                eprintln!("{} day {}: {}", solver.year(), solver.day(), message);
+                succeeded = false;
+                continue;
+            }
+        };
+        for row in batch::run(solver, &[source], parts) {
+            succeeded &= row.succeeded();
+            records.extend(row.records(solver, parts));
+        }
+    }
+    print!("{}", output::to_json(&records));
+    succeeded
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(message) => {
+            eprintln!("{}", message);
+            return ExitCode::FAILURE;
+        }
+    };
+    arith::set_policy(args.arith);
+    parallel::set_threads(args.threads.unwrap_or(0));
+    if args.command == Command::New {
+        // There is no solver to select yet; the year and day were parsed along with the command.
+        let (year, day) = (args.year.unwrap(), args.day.unwrap());
+        return match scaffold::new_day(&root_dir(), year, day) {
+            Ok(dir) => {
+                println!("Created {}", dir.display());
+                ExitCode::SUCCESS
+            }
+            Err(error) => {
+                eprintln!("Failed to create {} day {}: {}", year, day, error);
+                ExitCode::FAILURE
+            }
+        };
+    }
+
+    let solvers = SOLVERS
+        .iter()
+        .copied()
+        .filter(|solver| args.year.is_none_or(|year| solver.year() == year))
+        .filter(|solver| args.day.is_none_or(|day| solver.day() == day))
+        .collect::<Vec<_>>();
+    if solvers.is_empty() {
+        eprintln!("No solver matches the given year and day");
+        return ExitCode::FAILURE;
+    }
+    if args.input.is_some() && solvers.len() > 1 {
+        eprintln!("--input requires --year and --day to select a single day");
+        return ExitCode::FAILURE;
+    }
+    if args.command == Command::Batch && args.input.is_none() {
+        eprintln!("batch requires --input with a directory, a file or - for stdin");
+        return ExitCode::FAILURE;
+    }
+    let parts = match args.part {
+        Some(part) => vec![part],
+        None => Part::ALL.to_vec(),
+    };
+    if let Some(format) = args.render {
+        if args.json {
+            eprintln!("--render can't be combined with --json");
+            return ExitCode::FAILURE;
+        }
+        if args.command != Command::Run || solvers.len() > 1 {
+            eprintln!("--render requires --year and --day to select a single day to run");
+            return ExitCode::FAILURE;
+        }
+        if format == Format::Svg && parts.len() > 1 {
+            eprintln!("--render svg requires --part, as it writes a single document");
+            return ExitCode::FAILURE;
+        }
+    }
+
+    let succeeded = match args.command {
+        Command::Run => match args.render {
+            Some(format) => render(solvers[0], args.input.as_deref(), &parts, format),
+            None if args.json => run_json(&solvers, args.input.as_deref(), &parts),
+            None => {
+                let mut succeeded = true;
+                for &solver in &solvers {
+                    succeeded &= run(solver, args.input.as_deref(), &parts);
+                }
+                succeeded
+            }
+        },
+        Command::New => unreachable!("handled before selecting solvers"),
+        Command::Verify => verify_all(&args, &solvers, &parts),
+        Command::Bench => bench_all(&args, &solvers, &parts),
+        // Checked above: there is an input, so there is a single day.
+        Command::Batch => batch_all(
+            solvers[0],
+            args.input.as_deref().unwrap(),
+            &parts,
+            args.json,
+        ),
+    };
+
+    if succeeded {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
@@ -0,0 +1,307 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+const CARGO_TOML: &str = r#"[package]
+name = "aoc_{year}_day_{day}"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+aoc_common = { path = "../../common" }
+"#;
+
+const MAIN_RS: &str = r#"fn main() -> std::process::ExitCode {
+    aoc_common::solution::main::<aoc_{year}_day_{day}::Day>(env!("CARGO_MANIFEST_DIR"))
+}
+"#;
+
+const LIB_RS: &str = r#"//! {year} day {day}.
+
+#![warn(missing_docs)]
+
+use aoc_common::{Answer, Error, Result, Solution};
+
+/// The solution of the day's puzzle.
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = {year};
+    const DAY: u8 = {day};
+
+    type Input<'a> = Vec<&'a str>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        Ok(input.lines().collect())
+    }
+
+    fn part1(_input: &Self::Input<'_>) -> Result<Answer> {
+        Err(Error::NoSolution("part 1 is not solved yet".to_string()))
+    }
+
+    fn part2(_input: &Self::Input<'_>) -> Result<Answer> {
+        Err(Error::NoSolution("part 2 is not solved yet".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{input::fixture, solution::answer, Part};
+
+    use super::*;
+
+    fn example() -> String {
+        fixture(env!("CARGO_MANIFEST_DIR"), "example.txt")
+    }
+
+    #[test]
+    #[ignore = "needs the example in fixtures/example.txt and its answer"]
+    fn part1_example() {
+        assert_eq!(answer::<Day>(&example(), Part::One), Ok(Answer::Integer(0)));
+    }
+
+    #[test]
+    #[ignore = "needs the example in fixtures/example.txt and its answer"]
+    fn part2_example() {
+        assert_eq!(answer::<Day>(&example(), Part::Two), Ok(Answer::Integer(0)));
+    }
+}
+"#;
+
+const FUZZ_TARGET_RS: &str = r#"#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| aoc_fuzz::parse::<aoc_{year}_day_{day}::Day>(data));
+"#;
+
+const FUZZ_BIN: &str = r#"
+[[bin]]
+name = "parse_{year}_day_{day}"
+path = "fuzz_targets/parse_{year}_day_{day}.rs"
+test = false
+doc = false
+bench = false
+"#;
+
+fn fill(template: &str, year: u16, day: u8) -> String {
+    template
+        .replace("{year}", &year.to_string())
+        .replace("{day}", &day.to_string())
+}
+
+/// Creates the crate of a new day under `root`, with a skeleton solution, ignored example tests
+/// and an empty example fixture, and registers it with the runner and the fuzz targets. Returns
+/// the directory of the crate.
+///
+/// Every file it edits is read before anything is written, and what was written is undone if a
+/// later write fails, so an error leaves the tree as it was.
+pub fn new_day(root: &Path, year: u16, day: u8) -> io::Result<PathBuf> {
+    let dir = root.join(year.to_string()).join(format!("day_{}", day));
+    let fuzz = root.join("fuzz");
+    let target = fuzz
+        .join("fuzz_targets")
+        .join(fill("parse_{year}_day_{day}.rs", year, day));
+    for path in [&dir, &target] {
+        if path.exists() {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("{} already exists", path.display()),
+            ));
+        }
+    }
+    if !fuzz.join("fuzz_targets").is_dir() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("{} is not a directory", fuzz.join("fuzz_targets").display()),
+        ));
+    }
+
+    let dependency = fill(
+        r#"aoc_{year}_day_{day} = { path = "../{year}/day_{day}" }"#,
+        year,
+        day,
+    );
+    let solver = fill("    &Day::<aoc_{year}_day_{day}::Day>::SOLVER,", year, day);
+    let manifest = fuzz.join("Cargo.toml");
+    let mut edits = vec![];
+    for (path, line) in [
+        (root.join("aoc").join("Cargo.toml"), &dependency),
+        (root.join("aoc").join("src").join("solvers.rs"), &solver),
+        (manifest.clone(), &dependency),
+    ] {
+        let text = fs::read_to_string(&path).map_err(|error| {
+            io::Error::new(error.kind(), format!("{}: {}", path.display(), error))
+        })?;
+        let mut edited = register(&text, line, (year, day));
+        if path == manifest {
+            edited.push_str(&fill(FUZZ_BIN, year, day));
+        }
+        edits.push(Edit { path, text, edited });
+    }
+
+    let mut files = vec![
+        (dir.join("Cargo.toml"), fill(CARGO_TOML, year, day)),
+        (dir.join("src").join("main.rs"), fill(MAIN_RS, year, day)),
+        (dir.join("src").join("lib.rs"), fill(LIB_RS, year, day)),
+        (dir.join("fixtures").join("example.txt"), String::new()),
+    ];
+    files.push((target, fill(FUZZ_TARGET_RS, year, day)));
+    write_or_undo(&dir, &files, &edits)?;
+    Ok(dir)
+}
+
+/// A file to rewrite, with its text before and after.
+struct Edit {
+    path: PathBuf,
+    text: String,
+    edited: String,
+}
+
+/// Creates the crate directory `dir` and the new `files`, then applies `edits`. If any write fails,
+/// removes what was created and restores what was edited before returning the error.
+fn write_or_undo(dir: &Path, files: &[(PathBuf, String)], edits: &[Edit]) -> io::Result<()> {
+    let write = || -> io::Result<()> {
+        for (path, contents) in files {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(path, contents)?;
+        }
+        for edit in edits {
+            fs::write(&edit.path, &edit.edited)?;
+        }
+        Ok(())
+    };
+    let written = write();
+    if written.is_err() {
+        let _ = fs::remove_dir_all(dir);
+        for (path, _) in files {
+            let _ = fs::remove_file(path);
+        }
+        for edit in edits {
+            let _ = fs::write(&edit.path, &edit.text);
+        }
+    }
+    written
+}
+
+/// The year and day of the first `aoc_<year>_day_<day>` crate named in `line`.
+fn day_key(line: &str) -> Option<(u16, u8)> {
+    let (_, rest) = line.split_once("aoc_")?;
+    let (year, rest) = rest.split_once("_day_")?;
+    let digits = rest
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(rest.len());
+    Some((year.parse().ok()?, rest[..digits].parse().ok()?))
+}
+
+/// `text` with `line` inserted among the lines naming day crates, keeping them in order of year and
+/// day.
+fn register(text: &str, line: &str, key: (u16, u8)) -> String {
+    let mut lines = text.lines().collect::<Vec<_>>();
+    let keyed = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(index, line)| Some((index, day_key(line)?)))
+        .collect::<Vec<_>>();
+    let index = match keyed.iter().find(|&&(_, other)| other > key) {
+        Some(&(index, _)) => index,
+        None => keyed.last().map_or(lines.len(), |&(index, _)| index + 1),
+    };
+    lines.insert(index, line);
+    lines.join("\n") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scaffolds_and_registers_a_day() {
+        let root = std::env::temp_dir().join(format!("aoc_scaffold_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let real_root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+        for file in ["aoc/Cargo.toml", "aoc/src/solvers.rs", "fuzz/Cargo.toml"] {
+            fs::create_dir_all(root.join(file).parent().unwrap()).unwrap();
+            fs::copy(real_root.join(file), root.join(file)).unwrap();
+        }
+        fs::create_dir_all(root.join("fuzz/fuzz_targets")).unwrap();
+
+        let dir = new_day(&root, 2023, 6).unwrap();
+        assert_eq!(dir, root.join("2023").join("day_6"));
+        let lib = fs::read_to_string(dir.join("src/lib.rs")).unwrap();
+        assert!(lib.contains("const YEAR: u16 = 2023;\n    const DAY: u8 = 6;"));
+        assert!(dir.join("src/main.rs").is_file());
+        assert!(dir.join("fixtures/example.txt").is_file());
+        assert!(root.join("fuzz/fuzz_targets/parse_2023_day_6.rs").is_file());
+
+        let solvers = fs::read_to_string(root.join("aoc/src/solvers.rs")).unwrap();
+        assert!(solvers.contains(
+            "aoc_2023_day_5::Day>::SOLVER,\n    \
+             &Day::<aoc_2023_day_6::Day>::SOLVER,\n    \
+             &Day::<aoc_2023_day_7::Day>::SOLVER,"
+        ));
+        let manifest = fs::read_to_string(root.join("fuzz/Cargo.toml")).unwrap();
+        assert!(
+            manifest.contains("aoc_2023_day_6 = { path = \"../2023/day_6\" }\naoc_2023_day_7 = ")
+        );
+        assert!(manifest.contains("name = \"parse_2023_day_6\""));
+
+        let error = new_day(&root, 2023, 6).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::AlreadyExists);
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn failures_leave_the_tree_as_it_was() {
+        let root =
+            std::env::temp_dir().join(format!("aoc_scaffold_failure_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let real_root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+        for file in ["aoc/Cargo.toml", "aoc/src/solvers.rs"] {
+            fs::create_dir_all(root.join(file).parent().unwrap()).unwrap();
+            fs::copy(real_root.join(file), root.join(file)).unwrap();
+        }
+        fs::create_dir_all(root.join("fuzz/fuzz_targets")).unwrap();
+        let snapshot = || {
+            ["aoc/Cargo.toml", "aoc/src/solvers.rs"]
+                .map(|file| fs::read_to_string(root.join(file)).unwrap())
+        };
+        let before = snapshot();
+
+        // The fuzz manifest is missing, and it is only read after the others.
+        let error = new_day(&root, 2023, 6).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::NotFound);
+        assert_eq!(snapshot(), before);
+        assert!(!root.join("2023").exists());
+        assert_eq!(
+            fs::read_dir(root.join("fuzz/fuzz_targets"))
+                .unwrap()
+                .count(),
+            0
+        );
+
+        // A write that fails after others undoes them.
+        let dir = root.join("2023").join("day_6");
+        let solvers = root.join("aoc/src/solvers.rs");
+        let edits = [
+            Edit {
+                path: solvers.clone(),
+                text: before[1].clone(),
+                edited: "edited".to_string(),
+            },
+            Edit {
+                path: root.join("fuzz"),
+                text: String::new(),
+                edited: "a directory can't be written".to_string(),
+            },
+        ];
+        let files = [(dir.join("src/lib.rs"), "lib".to_string())];
+        assert!(write_or_undo(&dir, &files, &edits).is_err());
+        assert_eq!(snapshot(), before);
+        assert!(!dir.exists());
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
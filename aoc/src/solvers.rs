@@ -0,0 +1,152 @@
+use aoc_common::solution::{Day, Solver};
+
+pub const SOLVERS: &[&dyn Solver] = &[
+    &Day::<aoc_2023_day_1::Day>::SOLVER,
+    &Day::<aoc_2023_day_2::Day>::SOLVER,
+    &Day::<aoc_2023_day_3::Day>::SOLVER,
+    &Day::<aoc_2023_day_4::Day>::SOLVER,
+    &Day::<aoc_2023_day_5::Day>::SOLVER,
+    &Day::<aoc_2023_day_7::Day>::SOLVER,
+    &Day::<aoc_2023_day_8::Day>::SOLVER,
+    &Day::<aoc_2023_day_9::Day>::SOLVER,
+    &Day::<aoc_2023_day_10::Day>::SOLVER,
+    &Day::<aoc_2023_day_11::Day>::SOLVER,
+    &Day::<aoc_2023_day_12::Day>::SOLVER,
+    &Day::<aoc_2023_day_13::Day>::SOLVER,
+    &Day::<aoc_2023_day_14::Day>::SOLVER,
+    &Day::<aoc_2023_day_15::Day>::SOLVER,
+    &Day::<aoc_2023_day_16::Day>::SOLVER,
+    &Day::<aoc_2023_day_17::Day>::SOLVER,
+    &Day::<aoc_2023_day_18::Day>::SOLVER,
+    &Day::<aoc_2023_day_19::Day>::SOLVER,
+    &Day::<aoc_2023_day_22::Day>::SOLVER,
+    &Day::<aoc_2024_day_1::Day>::SOLVER,
+    &Day::<aoc_2024_day_2::Day>::SOLVER,
+    &Day::<aoc_2024_day_3::Day>::SOLVER,
+];
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        panic::{self, AssertUnwindSafe},
+        path::Path,
+    };
+
+    use aoc_common::{random::Rng, Part};
+
+    use super::*;
+
+    /// Bytes that mean something to some parser.
+    const INTERESTING: &[u8] = b"0123456789 \n\n,:;=-+#.()[]{}<>|LJF7S?~xmasRDLU";
+
+    /// Numbers at the limits of the integer types parsers use.
+    const HUGE: [&str; 6] = [
+        "255",
+        "2147483647",
+        "-2147483648",
+        "4294967295",
+        "18446744073709551615",
+        "99999999999999999999999",
+    ];
+
+    /// Inputs that once made a day panic or accept malformed input, by year and day. Each is tried as it is and mutated.
+    const REGRESSIONS: &[(u16, u8, &str)] = &[
+        (2023, 7, "32T3K 765 junk\n"),
+        (2023, 9, "\n"),
+        (2023, 9, "1 2 3\n\n4 5\n"),
+        (2023, 12, "# 0\n"),
+        (2023, 12, "???.### 1,4294967295,3\n"),
+        (2023, 15, "rn=1,ab-cd,qp=3\n"),
+        (2023, 15, "rn=1,qp=-3\n"),
+        (2023, 16, ""),
+        (2023, 22, ""),
+        (2023, 22, "0,0,4~4294967295,2,4\n"),
+        (2024, 1, "2147483647 -2147483648\n"),
+        (2024, 1, "3   4\n4   3 2\n"),
+    ];
+
+    /// A copy of `input` with a few random edits: bytes inserted, removed, replaced or repeated,
+    /// a number made huge, or the end cut off.
+    fn mutate(rng: &mut Rng, input: &[u8]) -> Vec<u8> {
+        let mut bytes = input.to_vec();
+        for _ in 0..rng.range(1..4) {
+            let at = rng.index(bytes.len() + 1);
+            let end = (at + rng.index(4)).min(bytes.len());
+            match rng.index(6) {
+                0 => bytes.insert(at, *rng.choose(INTERESTING)),
+                1 => drop(bytes.drain(at..end)),
+                2 if at < bytes.len() => bytes[at] = *rng.choose(INTERESTING),
+                3 => {
+                    let repeated = bytes[at..end].to_vec();
+                    bytes.splice(at..at, repeated);
+                }
+                4 => {
+                    // Replace the whole number around `at`, if there is one.
+                    let start = at
+                        - bytes[..at]
+                            .iter()
+                            .rev()
+                            .take_while(|b| b.is_ascii_digit())
+                            .count();
+                    let end = at
+                        + bytes[at..]
+                            .iter()
+                            .take_while(|b| b.is_ascii_digit())
+                            .count();
+                    bytes.splice(start..end, rng.choose(&HUGE).bytes());
+                }
+                _ => bytes.truncate(at),
+            }
+        }
+        bytes
+    }
+
+    /// Parsing mutated examples and random bytes, and solving both parts of what parses, may fail,
+    /// but must not panic.
+    #[test]
+    fn parsers_never_panic() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+        let mut rng = Rng::new(0);
+        // The first input every day panicked on.
+        let mut failures = vec![];
+        for solver in SOLVERS {
+            let fixtures = root
+                .join(solver.year().to_string())
+                .join(format!("day_{}", solver.day()))
+                .join("fixtures");
+            let mut inputs = vec![];
+            for entry in fs::read_dir(&fixtures).unwrap() {
+                let example = fs::read(entry.unwrap().path()).unwrap();
+                inputs.extend((0..300).map(|_| mutate(&mut rng, &example)));
+            }
+            for &(_, _, regression) in REGRESSIONS
+                .iter()
+                .filter(|&&(year, day, _)| (year, day) == (solver.year(), solver.day()))
+            {
+                inputs.push(regression.as_bytes().to_vec());
+                inputs.extend((0..100).map(|_| mutate(&mut rng, regression.as_bytes())));
+            }
+            inputs.extend((0..100).map(|_| {
+                (0..rng.index(40))
+                    .map(|_| *rng.choose(INTERESTING))
+                    .collect()
+            }));
+            let failure = inputs.into_iter().find_map(|input| {
+                let input = String::from_utf8_lossy(&input).into_owned();
+                panic::catch_unwind(AssertUnwindSafe(|| solver.solve(&input, &Part::ALL)))
+                    .is_err()
+                    .then_some(input)
+            });
+            if let Some(input) = failure {
+                failures.push(format!(
+                    "{} day {}:\n{}",
+                    solver.year(),
+                    solver.day(),
+                    input
+                ));
+            }
+        }
+        assert!(failures.is_empty(), "{}", failures.join("\n"));
+    }
+}
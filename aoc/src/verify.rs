@@ -0,0 +1,236 @@
+use std::collections::HashSet;
+
+use aoc_common::{
+    parse::{self, ParseError},
+    solution::Solver,
+    Answer, Part,
+};
+
+/// An answer we know to be right for our own input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub year: u16,
+    pub day: u8,
+    pub part: Part,
+    pub answer: Answer,
+}
+
+/// Parses a registry with one `<year> <day> <part> <answer>` entry per line. Blank lines and lines
+/// starting with `#` are skipped.
+pub fn parse_registry(input: &str) -> Result<Vec<Entry>, ParseError> {
+    let mut entries = Vec::<Entry>::new();
+    let mut seen = HashSet::new();
+    for line in input.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let mut tokens = line.split_whitespace();
+        let year = parse::next(input, line, &mut tokens, "year")?;
+        let day = parse::next(input, line, &mut tokens, "day")?;
+        let part_token = parse::next(input, line, &mut tokens, "part")?;
+        let part = Part::from_number(parse::number(input, part_token)?)
+            .ok_or_else(|| ParseError::at(input, part_token, "part 1 or 2"))?;
+        let answer = parse::next(input, line, &mut tokens, "answer")?;
+        if let Some(extra) = tokens.next() {
+            return Err(ParseError::at(input, extra, "end of line"));
+        }
+        let entry = Entry {
+            year: parse::number(input, year)?,
+            day: parse::number(input, day)?,
+            part,
+            answer: answer
+                .parse()
+                .map_or_else(|_| Answer::Text(answer.to_string()), Answer::Integer),
+        };
+        if !seen.insert((entry.year, entry.day, entry.part)) {
+            return Err(ParseError::at(input, line, "a part not registered before"));
+        }
+        entries.push(entry);
+    }
+    Ok(entries)
+}
+
+/// What became of one registered answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Pass,
+    Fail(Answer),
+    Error(String),
+}
+
+impl Outcome {
+    /// Compares `actual` with `expected` by how they print, so an integer registered for a text
+    /// answer still matches.
+    pub fn of(expected: &Answer, actual: Answer) -> Outcome {
+        if actual.to_string() == expected.to_string() {
+            Outcome::Pass
+        } else {
+            Outcome::Fail(actual)
+        }
+    }
+
+    fn status(&self) -> &'static str {
+        match self {
+            Outcome::Pass => "ok",
+            Outcome::Fail(_) => "FAIL",
+            Outcome::Error(_) => "ERROR",
+        }
+    }
+
+    /// How the actual answer differs from `expected`, empty on a pass.
+    fn diff(&self, expected: &Answer) -> String {
+        match (self, expected) {
+            (Outcome::Pass, _) => String::new(),
+            (Outcome::Fail(Answer::Integer(actual)), Answer::Integer(expected)) => {
+                match actual.checked_sub(*expected) {
+                    Some(difference) => format!("got {} ({:+})", actual, difference),
+                    None => format!("got {}, expected {}", actual, expected),
+                }
+            }
+            (Outcome::Fail(actual), _) => format!("got {}", actual),
+            (Outcome::Error(message), _) => message.clone(),
+        }
+    }
+}
+
+/// Recomputes every entry of `registry` that `solvers` can answer, with `solve` standing in for
+/// reading the day's input and answering the given parts. Prints a table and returns whether
+/// everything passed, which needs at least one entry to have been checked.
+pub fn verify(
+    solvers: &[&dyn Solver],
+    registry: &[Entry],
+    mut solve: impl FnMut(&dyn Solver, &[Part]) -> Result<Vec<aoc_common::Result<Answer>>, String>,
+) -> bool {
+    let mut rows = Vec::new();
+    for &solver in solvers {
+        let entries = registry
+            .iter()
+            .filter(|entry| entry.year == solver.year() && entry.day == solver.day())
+            .collect::<Vec<_>>();
+        if entries.is_empty() {
+            continue;
+        }
+        let parts = entries.iter().map(|entry| entry.part).collect::<Vec<_>>();
+        let outcomes: Vec<Outcome> = match solve(solver, &parts) {
+            Ok(answers) => entries
+                .iter()
+                .zip(answers)
+                .map(|(entry, answer)| match answer {
+                    Ok(answer) => Outcome::of(&entry.answer, answer),
+                    Err(error) => Outcome::Error(error.to_string()),
+                })
+                .collect(),
+            Err(message) => vec![Outcome::Error(message); entries.len()],
+        };
+        rows.extend(entries.into_iter().zip(outcomes));
+    }
+    print_table(&rows);
+    !rows.is_empty() && rows.iter().all(|(_, outcome)| *outcome == Outcome::Pass)
+}
+
+fn print_table(rows: &[(&Entry, Outcome)]) {
+    if rows.is_empty() {
+        eprintln!("Nothing was checked: no registered answers for the selected days and parts");
+        return;
+    }
+    let header = ["year", "day", "part", "status", "expected", "diff"];
+    let cells = rows
+        .iter()
+        .map(|(entry, outcome)| {
+            [
+                entry.year.to_string(),
+                entry.day.to_string(),
+                entry.part.to_string(),
+                outcome.status().to_string(),
+                entry.answer.to_string(),
+                outcome.diff(&entry.answer),
+            ]
+        })
+        .collect::<Vec<_>>();
+    let mut widths = header.map(str::len);
+    for row in &cells {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    let print_row = |row: &[&str]| {
+        let line = row
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{:width$}", cell, width = width))
+            .collect::<Vec<_>>()
+            .join("  ");
+        println!("{}", line.trim_end());
+    };
+    print_row(&header);
+    for row in &cells {
+        print_row(&row.each_ref().map(String::as_str));
+    }
+    let passed = rows
+        .iter()
+        .filter(|(_, outcome)| *outcome == Outcome::Pass)
+        .count();
+    println!("{} passed, {} failed", passed, rows.len() - passed);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registry_entries() {
+        let registry = "# year day part answer\n2023 19 1 19114\n\n2023 19 2 abc\n";
+        assert_eq!(
+            parse_registry(registry),
+            Ok(vec![
+                Entry {
+                    year: 2023,
+                    day: 19,
+                    part: Part::One,
+                    answer: Answer::Integer(19114),
+                },
+                Entry {
+                    year: 2023,
+                    day: 19,
+                    part: Part::Two,
+                    answer: Answer::Text("abc".to_string()),
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn registry_errors() {
+        let error = parse_registry("2023 19 3 1").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 9));
+        let error = parse_registry("2023 19 1 1\n2023 19 1 2").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        let error = parse_registry("2023 19").unwrap_err();
+        assert_eq!(error.expected, "part");
+    }
+
+    #[test]
+    fn outcome_diff() {
+        let expected = Answer::Integer(10);
+        assert_eq!(Outcome::of(&expected, Answer::Integer(10)), Outcome::Pass);
+        assert_eq!(
+            Outcome::of(&expected, Answer::Text("10".into())),
+            Outcome::Pass
+        );
+        let outcome = Outcome::of(&expected, Answer::Integer(7));
+        assert_eq!(outcome.diff(&expected), "got 7 (-3)");
+
+        let expected = Answer::Integer(i128::MIN);
+        let outcome = Outcome::of(&expected, Answer::Integer(1));
+        assert_eq!(
+            outcome.diff(&expected),
+            format!("got 1, expected {}", i128::MIN)
+        );
+    }
+
+    #[test]
+    fn nothing_checked_fails() {
+        assert!(!verify(&[], &[], |_, _| unreachable!("no entry to solve")));
+    }
+}
@@ -0,0 +1,152 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use crate::{Error, Result};
+
+/// What to do when a result doesn't fit the integer type a day keeps it in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Policy {
+    /// Fail with an [`Error::Overflow`] naming the step.
+    #[default]
+    Checked,
+    /// Clamp the result to the range of the type.
+    Saturating,
+    /// Carry on as if the type were `i128`, failing only if that overflows too.
+    Wide,
+}
+
+/// An integer type whose range the arithmetic of a [`Policy`] is bounded by.
+pub trait Bounded {
+    /// The smallest value of the type.
+    const MIN: i128;
+    /// The largest value of the type.
+    const MAX: i128;
+}
+
+macro_rules! impl_bounded {
+    ($($integer:ty),*) => {
+        $(
+            impl Bounded for $integer {
+                const MIN: i128 = <$integer>::MIN as i128;
+                const MAX: i128 = <$integer>::MAX as i128;
+            }
+        )*
+    };
+}
+
+impl_bounded!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+impl Policy {
+    /// Every policy.
+    pub const ALL: [Policy; 3] = [Policy::Checked, Policy::Saturating, Policy::Wide];
+
+    /// The policy named `name` on the command line.
+    pub fn from_name(name: &str) -> Option<Policy> {
+        Policy::ALL.into_iter().find(|policy| policy.name() == name)
+    }
+
+    /// The name of the policy on the command line.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Policy::Checked => "checked",
+            Policy::Saturating => "saturating",
+            Policy::Wide => "wide",
+        }
+    }
+
+    /// Fits the exact result of `step` into `T`, where `None` means it didn't even fit an `i128`.
+    pub fn fit<T: Bounded>(self, value: Option<i128>, step: &str) -> Result<i128> {
+        let overflow = || Error::Overflow(step.to_string());
+        let value = value.ok_or_else(overflow)?;
+        match self {
+            _ if (T::MIN..=T::MAX).contains(&value) => Ok(value),
+            Policy::Checked => Err(overflow()),
+            Policy::Saturating => Ok(value.clamp(T::MIN, T::MAX)),
+            Policy::Wide => Ok(value),
+        }
+    }
+
+    /// `a + b`, fitted into `T`.
+    pub fn add<T: Bounded>(self, a: i128, b: i128, step: &str) -> Result<i128> {
+        self.fit::<T>(a.checked_add(b), step)
+    }
+
+    /// `a - b`, fitted into `T`.
+    pub fn sub<T: Bounded>(self, a: i128, b: i128, step: &str) -> Result<i128> {
+        self.fit::<T>(a.checked_sub(b), step)
+    }
+
+    /// `a * b`, fitted into `T`.
+    pub fn mul<T: Bounded>(self, a: i128, b: i128, step: &str) -> Result<i128> {
+        self.fit::<T>(a.checked_mul(b), step)
+    }
+
+    /// The sum of `values`, fitted into `T` after every addition.
+    pub fn sum<T: Bounded>(
+        self,
+        values: impl IntoIterator<Item = i128>,
+        step: &str,
+    ) -> Result<i128> {
+        values
+            .into_iter()
+            .try_fold(0, |sum, value| self.add::<T>(sum, value, step))
+    }
+
+    /// The product of `values`, fitted into `T` after every multiplication.
+    pub fn product<T: Bounded>(
+        self,
+        values: impl IntoIterator<Item = i128>,
+        step: &str,
+    ) -> Result<i128> {
+        values
+            .into_iter()
+            .try_fold(1, |product, value| self.mul::<T>(product, value, step))
+    }
+}
+
+static POLICY: AtomicU8 = AtomicU8::new(Policy::Checked as u8);
+
+/// The policy of this run: [`Policy::Checked`] unless [`set_policy`] said otherwise.
+pub fn policy() -> Policy {
+    Policy::ALL[POLICY.load(Ordering::Relaxed) as usize]
+}
+
+/// Sets the policy for the rest of the run, typically from the command line.
+pub fn set_policy(policy: Policy) {
+    POLICY.store(policy as u8, Ordering::Relaxed);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn policies_on_overflow() {
+        let big = i128::from(u32::MAX);
+        let overflow = Err(Error::Overflow("step".to_string()));
+        assert_eq!(Policy::Checked.add::<u32>(big - 1, 1, "step"), Ok(big));
+        assert_eq!(Policy::Checked.add::<u32>(big, 1, "step"), overflow);
+        assert_eq!(Policy::Saturating.add::<u32>(big, 1, "step"), Ok(big));
+        assert_eq!(Policy::Saturating.sub::<u32>(0, 1, "step"), Ok(0));
+        assert_eq!(Policy::Wide.add::<u32>(big, 1, "step"), Ok(big + 1));
+        assert_eq!(Policy::Wide.mul::<u32>(i128::MAX, 2, "step"), overflow);
+
+        assert_eq!(Policy::Checked.sum::<i32>([1, 2, 3], "step"), Ok(6));
+        assert_eq!(Policy::Checked.product::<i32>([], "step"), Ok(1));
+        assert_eq!(
+            Policy::Checked.product::<i32>([1 << 16, 1 << 15], "step"),
+            overflow
+        );
+        assert_eq!(
+            Policy::Saturating.product::<i32>([-(1 << 16), 1 << 15, 2], "step"),
+            Ok(i32::MIN.into())
+        );
+    }
+
+    #[test]
+    fn policy_names() {
+        for policy in Policy::ALL {
+            assert_eq!(Policy::from_name(policy.name()), Some(policy));
+        }
+        assert_eq!(Policy::from_name("wrapping"), None);
+    }
+}
@@ -0,0 +1,287 @@
+use std::{
+    fs, io,
+    panic::{self, AssertUnwindSafe},
+    path::Path,
+    time::Duration,
+};
+
+use crate::{input::Source, output::Record, solution::Solver, Answer, Part};
+
+/// The inputs named by a command line argument: stdin for `-`, every file directly inside a
+/// directory in order of name, or else the file itself.
+pub fn sources(arg: &str) -> io::Result<Vec<Source>> {
+    let path = Path::new(arg);
+    if arg == "-" || !path.is_dir() {
+        return Ok(vec![Source::from_arg(arg)]);
+    }
+    let mut paths = vec![];
+    for entry in fs::read_dir(path)? {
+        let path = entry?.path();
+        if path.is_file() {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    Ok(paths.into_iter().map(Source::File).collect())
+}
+
+/// The answer to one part or why it failed, with the time it took.
+pub type Outcome = (Result<Answer, String>, Duration);
+
+/// What became of one input of a batch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Row {
+    /// Where the input was read from.
+    pub source: Source,
+    /// The outcome of every part, or why the input couldn't be read or parsed.
+    pub answers: Result<Vec<Outcome>, String>,
+    /// Time spent parsing, but not reading.
+    pub parse: Duration,
+}
+
+impl Row {
+    /// Whether the input was parsed and every part answered.
+    pub fn succeeded(&self) -> bool {
+        self.answers
+            .as_ref()
+            .is_ok_and(|answers| answers.iter().all(|(answer, _)| answer.is_ok()))
+    }
+
+    /// Time spent parsing and answering.
+    pub fn time(&self) -> Duration {
+        let answering = self.answers.iter().flatten().map(|&(_, elapsed)| elapsed);
+        self.parse + answering.sum::<Duration>()
+    }
+
+    /// A record of every one of `parts` answered by `solver`. If the input couldn't be read or
+    /// parsed, every part records that error.
+    pub fn records(&self, solver: &dyn Solver, parts: &[Part]) -> Vec<Record> {
+        let answers = match &self.answers {
+            Ok(answers) => answers.clone(),
+            Err(message) => vec![(Err(message.clone()), Duration::ZERO); parts.len()],
+        };
+        parts
+            .iter()
+            .zip(answers)
+            .map(|(&part, (answer, elapsed))| Record {
+                year: solver.year(),
+                day: solver.day(),
+                part,
+                input: self.source.to_string(),
+                answer,
+                elapsed,
+            })
+            .collect()
+    }
+}
+
+/// Answers `parts` of every input in `sources`, carrying on past the ones that fail or panic.
+pub fn run(solver: &dyn Solver, sources: &[Source], parts: &[Part]) -> Vec<Row> {
+    sources
+        .iter()
+        .map(|source| {
+            let solved = source
+                .read()
+                .map_err(|error| error.to_string())
+                .and_then(|input| {
+                    panic::catch_unwind(AssertUnwindSafe(|| solver.solve_timed(&input, parts)))
+                        .map_err(|_| "solver panicked".to_string())?
+                        .map_err(|error| error.to_string())
+                });
+            let (answers, parse) = match solved {
+                Ok((answers, timings)) => (
+                    Ok(answers
+                        .into_iter()
+                        .map(|answer| answer.map_err(|error| error.to_string()))
+                        .zip(timings.parts)
+                        .collect()),
+                    timings.parse,
+                ),
+                Err(message) => (Err(message), Duration::ZERO),
+            };
+            Row {
+                source: source.clone(),
+                answers,
+                parse,
+            }
+        })
+        .collect()
+}
+
+/// A table with a line per row naming the input, then the answer to each of `parts` and the time
+/// taken. Errors stand in for the answers they prevented.
+pub fn table(rows: &[Row], parts: &[Part]) -> String {
+    let header = std::iter::once("input".to_string())
+        .chain(parts.iter().map(|part| format!("part {}", part)))
+        .chain(["time".to_string()])
+        .collect::<Vec<_>>();
+    let mut lines = vec![header];
+    for row in rows {
+        let mut line = vec![row.source.to_string()];
+        match &row.answers {
+            Ok(answers) => {
+                line.extend(answers.iter().map(|(answer, _)| match answer {
+                    Ok(answer) => answer.to_string(),
+                    Err(message) => format!("error: {}", message),
+                }));
+                line.push(format!("{:.2?}", row.time()));
+            }
+            Err(message) => {
+                line.push(format!("error: {}", message));
+                line.extend(vec!["-".to_string(); parts.len()]);
+            }
+        }
+        lines.push(line);
+    }
+
+    let mut widths = vec![0; lines[0].len()];
+    for line in &lines {
+        for (width, cell) in widths.iter_mut().zip(line) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    let mut result = String::new();
+    for line in lines {
+        let cells = line
+            .iter()
+            .zip(&widths)
+            .map(|(cell, &width)| format!("{:<width$}", cell, width = width))
+            .collect::<Vec<_>>();
+        result.push_str(cells.join("  ").trim_end());
+        result.push('\n');
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::{
+        parse,
+        solution::{Day, Solution},
+        Error, Result,
+    };
+
+    /// Sums the numbers on each line; part 2 fails unless the sum is even.
+    struct Sum;
+
+    impl Solution for Sum {
+        const YEAR: u16 = 2000;
+        const DAY: u8 = 1;
+
+        type Input<'a> = u64;
+
+        fn parse(input: &str) -> Result<u64> {
+            let numbers = input
+                .split_whitespace()
+                .map(|token| parse::number::<u64>(input, token))
+                .collect::<std::result::Result<Vec<_>, _>>()?;
+            Ok(numbers.iter().sum())
+        }
+
+        fn part1(input: &u64) -> Result<Answer> {
+            Ok((*input).into())
+        }
+
+        fn part2(input: &u64) -> Result<Answer> {
+            match input % 2 {
+                0 => Ok((input / 2).into()),
+                _ => Err(Error::NoSolution("odd sum".to_string())),
+            }
+        }
+    }
+
+    #[test]
+    fn run_over_a_directory() {
+        let dir = std::env::temp_dir().join(format!("aoc_batch_{}", std::process::id()));
+        fs::create_dir_all(dir.join("nested")).unwrap();
+        for (name, contents) in [("b.txt", "1 2 3"), ("a.txt", "4"), ("c.txt", "x")] {
+            fs::write(dir.join(name), contents).unwrap();
+        }
+
+        let sources = sources(dir.to_str().unwrap()).unwrap();
+        let names = ["a.txt", "b.txt", "c.txt"].map(|name| Source::File(dir.join(name)));
+        assert_eq!(sources, names);
+        assert_eq!(super::sources("-").unwrap(), [Source::Stdin]);
+
+        let rows = run(&Day::<Sum>::SOLVER, &sources, &Part::ALL);
+        fs::remove_dir_all(&dir).unwrap();
+        let answers = rows
+            .iter()
+            .map(|row| {
+                let answers = row.answers.as_ref().map_err(drop)?;
+                Ok(answers.iter().map(|(answer, _)| answer.clone()).collect())
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            answers,
+            [
+                Ok(vec![Ok(Answer::Integer(4)), Ok(Answer::Integer(2))]),
+                Ok(vec![Ok(Answer::Integer(6)), Ok(Answer::Integer(3))]),
+                Err(()),
+            ]
+        );
+        assert_eq!(
+            rows.iter().map(Row::succeeded).collect::<Vec<_>>(),
+            [true, true, false]
+        );
+    }
+
+    #[test]
+    fn table_lines_up_columns() {
+        let row = |name: &str, answers| Row {
+            source: Source::File(PathBuf::from(name)),
+            answers,
+            parse: Duration::from_micros(400),
+        };
+        let elapsed = Duration::from_micros(300);
+        let rows = [
+            row(
+                "long_name.txt",
+                Ok(vec![
+                    (Ok(Answer::Integer(5)), elapsed),
+                    (Err("odd sum".to_string()), elapsed),
+                ]),
+            ),
+            row("a", Err("bad input".to_string())),
+        ];
+        assert_eq!(
+            table(&rows, &Part::ALL),
+            "input          part 1            part 2          time\n\
+             long_name.txt  5                 error: odd sum  1.00ms\n\
+             a              error: bad input  -               -\n"
+        );
+    }
+
+    #[test]
+    fn records_of_rows() {
+        let solver = &Day::<Sum>::SOLVER;
+        let row = Row {
+            source: Source::Stdin,
+            answers: Err("bad input".to_string()),
+            parse: Duration::ZERO,
+        };
+        let records = row.records(solver, &Part::ALL);
+        assert_eq!(records.len(), 2);
+        assert!(records.iter().all(|record| record.answer_type() == "error"));
+
+        let row = Row {
+            answers: Ok(vec![(Ok(Answer::Integer(5)), Duration::from_micros(300))]),
+            ..row
+        };
+        assert_eq!(
+            row.records(solver, &[Part::Two]),
+            [Record {
+                year: 2000,
+                day: 1,
+                part: Part::Two,
+                input: "stdin".to_string(),
+                answer: Ok(Answer::Integer(5)),
+                elapsed: Duration::from_micros(300),
+            }]
+        );
+        assert_eq!(row.time(), Duration::from_micros(300));
+    }
+}
@@ -0,0 +1,182 @@
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    hash::Hash,
+};
+
+/// How a state seen before is recognized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// Keeps every state in a hash map. Steps through the sequence once, but holds all of it.
+    Hashed,
+    /// Brent's algorithm. Holds two states at a time, but steps through the sequence up to three
+    /// times.
+    Brent,
+}
+
+/// Where a sequence of states starts repeating.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// The first step whose state comes back later.
+    pub start: usize,
+    /// The number of steps until it comes back.
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The earliest step with the same state as step `n`.
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+}
+
+/// Finds the cycle of the states from `initial` on, each made from the previous one by `step`.
+/// Never returns if the states never repeat.
+pub fn find<S: Clone + Eq + Hash>(initial: &S, step: impl FnMut(&S) -> S, mode: Mode) -> Cycle {
+    let cycle = match mode {
+        Mode::Hashed => hashed(initial.clone(), step, usize::MAX).0,
+        Mode::Brent => brent(initial, step, usize::MAX),
+    };
+    cycle.expect("the sequence is longer than usize::MAX")
+}
+
+/// The state after `n` steps from `initial`, skipping over whole cycles once one is found.
+pub fn state_at<S: Clone + Eq + Hash>(
+    initial: S,
+    mut step: impl FnMut(&S) -> S,
+    n: usize,
+    mode: Mode,
+) -> S {
+    match mode {
+        Mode::Hashed => {
+            let (cycle, mut history) = hashed(initial, step, n);
+            let index = cycle.map_or(n, |cycle| cycle.reduce(n));
+            history.swap_remove(index)
+        }
+        Mode::Brent => {
+            let steps = brent(&initial, &mut step, n).map_or(n, |cycle| cycle.reduce(n));
+            (0..steps).fold(initial, |state, _| step(&state))
+        }
+    }
+}
+
+/// Steps until a state repeats or `limit` steps are taken, returning the cycle if one was found
+/// and every state seen.
+fn hashed<S: Clone + Eq + Hash>(
+    initial: S,
+    mut step: impl FnMut(&S) -> S,
+    limit: usize,
+) -> (Option<Cycle>, Vec<S>) {
+    let mut indices = HashMap::new();
+    let mut history = vec![initial];
+    for index in 0..limit {
+        let state = &history[index];
+        match indices.entry(state.clone()) {
+            Entry::Occupied(entry) => {
+                let start = *entry.get();
+                history.truncate(index);
+                return (
+                    Some(Cycle {
+                        start,
+                        length: index - start,
+                    }),
+                    history,
+                );
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(index);
+            }
+        }
+        let next = step(state);
+        history.push(next);
+    }
+    (None, history)
+}
+
+/// Brent's algorithm, giving up once the leading state is `limit` steps in.
+fn brent<S: Clone + Eq>(initial: &S, mut step: impl FnMut(&S) -> S, limit: usize) -> Option<Cycle> {
+    // Find the length: the leading state waits at powers of two for the trailing one to catch up.
+    let mut power = 1;
+    let mut length = 1;
+    let mut waiting = initial.clone();
+    let mut leading = step(initial);
+    let mut steps = 1;
+    while waiting != leading {
+        if steps >= limit {
+            return None;
+        }
+        if power == length {
+            waiting = leading.clone();
+            power *= 2;
+            length = 0;
+        }
+        leading = step(&leading);
+        length += 1;
+        steps += 1;
+    }
+
+    // Find the start: walk two states `length` steps apart until they meet.
+    let mut trailing = initial.clone();
+    let mut leading = (0..length).fold(initial.clone(), |state, _| step(&state));
+    let mut start = 0;
+    while trailing != leading {
+        trailing = step(&trailing);
+        leading = step(&leading);
+        start += 1;
+    }
+    Some(Cycle { start, length })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 0, 1, 2, ..., 9, then 3 to 9 over and over.
+    fn step(&state: &u32) -> u32 {
+        if state == 9 {
+            3
+        } else {
+            state + 1
+        }
+    }
+
+    #[test]
+    fn find_in_both_modes() {
+        let expected = Cycle {
+            start: 3,
+            length: 7,
+        };
+        assert_eq!(find(&0, step, Mode::Hashed), expected);
+        assert_eq!(find(&0, step, Mode::Brent), expected);
+        assert_eq!(
+            find(&5, step, Mode::Brent),
+            Cycle {
+                start: 0,
+                length: 7,
+            }
+        );
+        assert_eq!(find(&3, |&state| state, Mode::Hashed).length, 1);
+    }
+
+    #[test]
+    fn state_at_matches_stepping() {
+        for n in [0, 1, 2, 3, 9, 10, 11, 17, 1_000_000_000] {
+            let expected = if n < 10 {
+                n as u32
+            } else {
+                3 + (n as u32 - 3) % 7
+            };
+            assert_eq!(state_at(0, step, n, Mode::Hashed), expected, "{}", n);
+            assert_eq!(state_at(0, step, n, Mode::Brent), expected, "{}", n);
+        }
+    }
+
+    #[test]
+    fn state_at_never_repeating() {
+        assert_eq!(state_at(0u64, |&state| state + 1, 100, Mode::Hashed), 100);
+        assert_eq!(state_at(0u64, |&state| state + 1, 100, Mode::Brent), 100);
+    }
+}
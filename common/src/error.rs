@@ -0,0 +1,36 @@
+use std::fmt::{Display, Formatter};
+
+use crate::parse::ParseError;
+
+/// A result failing with an [`Error`] unless told otherwise.
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Why a solution failed to produce an answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The input is malformed.
+    Parse(ParseError),
+    /// The input is well formed but has no answer, e.g. no path reaches the goal.
+    NoSolution(String),
+    /// A result didn't fit its integer type under the [`Policy`](crate::arith::Policy) of the
+    /// run. Names the step that overflowed.
+    Overflow(String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parse(error) => write!(f, "Parse error at {}", error),
+            Error::NoSolution(reason) => write!(f, "No solution: {}", reason),
+            Error::Overflow(step) => write!(f, "Overflow in {}", step),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<ParseError> for Error {
+    fn from(error: ParseError) -> Self {
+        Error::Parse(error)
+    }
+}
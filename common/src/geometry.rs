@@ -0,0 +1,145 @@
+use std::fmt::{Display, Formatter};
+
+/// A `(row, column)` index into a grid, which can't go above or left of the origin.
+pub type Position = (usize, usize);
+
+/// A `(row, column)` point on the unbounded plane.
+pub type Point = (i64, i64);
+
+/// One of the four directions on a grid, where `Up` decreases the row and `Left` the column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    /// Towards row 0.
+    Up,
+    /// Towards the last row.
+    Down,
+    /// Towards column 0.
+    Left,
+    /// Towards the last column.
+    Right,
+}
+
+impl Direction {
+    /// Every direction, once.
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ];
+
+    /// The direction pointing the other way.
+    pub fn opposite(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+        }
+    }
+
+    /// The direction after a quarter turn counterclockwise.
+    pub fn turn_left(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Left,
+            Direction::Left => Direction::Down,
+            Direction::Down => Direction::Right,
+            Direction::Right => Direction::Up,
+        }
+    }
+
+    /// The direction after a quarter turn clockwise.
+    pub fn turn_right(self) -> Direction {
+        self.turn_left().opposite()
+    }
+
+    /// Whether the direction is up or down.
+    pub fn is_vertical(self) -> bool {
+        matches!(self, Direction::Up | Direction::Down)
+    }
+
+    /// The `(row, column)` change of one step.
+    pub fn offset(self) -> (i64, i64) {
+        match self {
+            Direction::Up => (-1, 0),
+            Direction::Down => (1, 0),
+            Direction::Left => (0, -1),
+            Direction::Right => (0, 1),
+        }
+    }
+
+    /// Moves `point` by `distance` steps.
+    pub fn step(self, (row, col): Point, distance: i64) -> Point {
+        let (d_row, d_col) = self.offset();
+        (row + d_row * distance, col + d_col * distance)
+    }
+
+    /// Moves `position` by one step, or returns `None` if that leaves the first quadrant. Use
+    /// [`Grid::step`](crate::Grid::step) to also check the far edges.
+    pub fn checked_step(self, (row, col): Position) -> Option<Position> {
+        let (d_row, d_col) = self.offset();
+        Some((
+            row.checked_add_signed(d_row as isize)?,
+            col.checked_add_signed(d_col as isize)?,
+        ))
+    }
+}
+
+/// Prints an arrow pointing in the direction.
+impl Display for Direction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let arrow = match self {
+            Direction::Up => '^',
+            Direction::Down => 'v',
+            Direction::Left => '<',
+            Direction::Right => '>',
+        };
+        write!(f, "{}", arrow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turns() {
+        for direction in Direction::ALL {
+            assert_eq!(direction.turn_left().turn_right(), direction);
+            assert_eq!(direction.turn_right().turn_right(), direction.opposite());
+            assert_eq!(direction.opposite().opposite(), direction);
+            assert_ne!(direction.turn_left().is_vertical(), direction.is_vertical());
+        }
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Left.turn_right(), Direction::Up);
+        assert_eq!(Direction::Right.opposite(), Direction::Left);
+    }
+
+    #[test]
+    fn all_directions_once() {
+        let mut offsets = Direction::ALL.map(Direction::offset);
+        offsets.sort();
+        assert_eq!(offsets, [(-1, 0), (0, -1), (0, 1), (1, 0)]);
+    }
+
+    #[test]
+    fn steps_at_the_bounds() {
+        assert_eq!(Direction::Up.checked_step((0, 5)), None);
+        assert_eq!(Direction::Left.checked_step((5, 0)), None);
+        assert_eq!(Direction::Down.checked_step((0, 0)), Some((1, 0)));
+        assert_eq!(Direction::Right.checked_step((0, 0)), Some((0, 1)));
+        assert_eq!(Direction::Down.checked_step((usize::MAX, 0)), None);
+        assert_eq!(Direction::Right.checked_step((0, usize::MAX)), None);
+        assert_eq!(
+            Direction::Up.checked_step((usize::MAX, usize::MAX)),
+            Some((usize::MAX - 1, usize::MAX))
+        );
+
+        assert_eq!(Direction::Up.step((0, 0), 3), (-3, 0));
+        assert_eq!(Direction::Left.step((0, 0), 1), (0, -1));
+        assert_eq!(Direction::Down.step((i64::MAX - 2, 0), 2), (i64::MAX, 0));
+        assert_eq!(Direction::Right.step((0, i64::MIN + 1), -1), (0, i64::MIN));
+        assert_eq!(Direction::Right.step((4, 7), 0), (4, 7));
+    }
+}
@@ -0,0 +1,377 @@
+use std::{
+    fmt::{Display, Formatter},
+    ops::{Index, IndexMut},
+};
+
+pub use crate::geometry::Position;
+use crate::{geometry::Direction, parse::ParseError};
+
+/// A rectangular grid of cells, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from its cells in row-major order.
+    ///
+    /// Panics if `cells` doesn't hold exactly `width * height` cells.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(cells.len(), width * height, "Grid size mismatch");
+        Self {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    /// A grid with every cell set to `value`.
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::new(width, height, vec![value; width * height])
+    }
+
+    /// Parses one row per line, mapping every character to a cell with `cell`. A character it
+    /// maps to `None` is reported as not being `expected`.
+    pub fn parse(
+        input: &str,
+        expected: &str,
+        cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        Self::parse_within(input, input, expected, cell)
+    }
+
+    /// Like [`Grid::parse`], but for a `section` of `input` that holds only the grid, so that
+    /// errors are located within the whole input.
+    pub fn parse_within(
+        input: &str,
+        section: &str,
+        expected: &str,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let mut cells = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+        for line in section.lines() {
+            let len = cells.len();
+            for (index, c) in line.char_indices() {
+                let error = || ParseError::at(input, &line[index..], expected);
+                cells.push(cell(c).ok_or_else(error)?);
+            }
+            let row_width = cells.len() - len;
+            match width {
+                Some(width) if width != row_width => {
+                    let expected = format!("row of {} cells", width);
+                    return Err(ParseError::at(input, line, expected));
+                }
+                _ => width = Some(row_width),
+            }
+            height += 1;
+        }
+        Ok(Self::new(width.unwrap_or(0), height, cells))
+    }
+
+    /// The number of columns.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// The number of rows.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether `(row, col)` is inside the grid.
+    pub fn contains(&self, (row, col): Position) -> bool {
+        row < self.height && col < self.width
+    }
+
+    /// The cell at `position`, or `None` outside the grid.
+    pub fn get(&self, position: Position) -> Option<&T> {
+        self.contains(position)
+            .then(|| &self.cells[position.0 * self.width + position.1])
+    }
+
+    /// The cell at `position` to change, or `None` outside the grid.
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        if self.contains(position) {
+            Some(&mut self.cells[position.0 * self.width + position.1])
+        } else {
+            None
+        }
+    }
+
+    /// All positions, row by row.
+    pub fn positions(&self) -> impl DoubleEndedIterator<Item = Position> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| (row, col)))
+    }
+
+    /// All cells, row by row.
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    /// Every cell to change, row by row.
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.cells.iter_mut()
+    }
+
+    /// All cells with their positions, row by row.
+    pub fn enumerate(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// Moves `position` one step in `direction`, or returns `None` if that leaves the grid.
+    pub fn step(&self, position: Position, direction: Direction) -> Option<Position> {
+        direction
+            .checked_step(position)
+            .filter(|&position| self.contains(position))
+    }
+
+    /// The up to 4 positions sharing an edge with `position`.
+    pub fn neighbors4(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |direction| self.step(position, direction))
+    }
+
+    /// The up to 8 positions sharing an edge or a corner with `position`.
+    pub fn neighbors8(&self, (row, col): Position) -> impl Iterator<Item = Position> + '_ {
+        [
+            (-1, -1),
+            (-1, 0),
+            (-1, 1),
+            (0, -1),
+            (0, 1),
+            (1, -1),
+            (1, 0),
+            (1, 1),
+        ]
+        .into_iter()
+        .filter_map(move |offset| self.offset((row, col), offset))
+    }
+
+    fn offset(&self, (row, col): Position, (d_row, d_col): (isize, isize)) -> Option<Position> {
+        let position = (
+            row.checked_add_signed(d_row)?,
+            col.checked_add_signed(d_col)?,
+        );
+        self.contains(position).then_some(position)
+    }
+
+    /// The cells of row `row`. Panics if it is out of bounds.
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    /// The cells of row `row` to change. Panics if it is out of bounds.
+    pub fn row_mut(&mut self, row: usize) -> &mut [T] {
+        &mut self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    /// Every row, from the top.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|row| self.row(row))
+    }
+
+    /// The cells of column `col`, top to bottom.
+    pub fn column(&self, col: usize) -> impl DoubleEndedIterator<Item = &T> {
+        assert!(col < self.width, "Column {} out of bounds", col);
+        // A grid without rows has no cells, even in columns within its width.
+        self.cells
+            .get(col..)
+            .unwrap_or(&[])
+            .iter()
+            .step_by(self.width)
+    }
+
+    /// Every column, from the left.
+    pub fn columns(&self) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &T>> {
+        (0..self.width).map(|col| self.column(col))
+    }
+
+    /// A grid of the same shape with `f` applied to every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+
+    /// Flips the grid over its main diagonal, so rows become columns.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..self.width)
+            .flat_map(|col| self.column(col).cloned())
+            .collect();
+        Self::new(self.height, self.width, cells)
+    }
+
+    /// Rotates the grid a quarter turn clockwise, so the left column becomes the top row.
+    pub fn rotate_clockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..self.width)
+            .flat_map(|col| self.column(col).rev().cloned())
+            .collect();
+        Self::new(self.height, self.width, cells)
+    }
+
+    /// Rotates the grid a quarter turn counterclockwise, so the right column becomes the top row.
+    pub fn rotate_counterclockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..self.width)
+            .rev()
+            .flat_map(|col| self.column(col).cloned())
+            .collect();
+        Self::new(self.height, self.width, cells)
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &T {
+        self.get(position)
+            .unwrap_or_else(|| panic!("Position {:?} out of bounds", position))
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut T {
+        self.get_mut(position)
+            .unwrap_or_else(|| panic!("Position {:?} out of bounds", position))
+    }
+}
+
+/// Prints one line per row, with no separator between cells.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 2 rows of 3 cells:
+    /// ```text
+    /// abc
+    /// def
+    /// ```
+    fn letters() -> Grid<char> {
+        Grid::parse("abc\ndef\n", "a letter", Some).unwrap()
+    }
+
+    #[test]
+    fn transpose_and_columns() {
+        let grid = letters();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(grid.column(2).rev().collect::<String>(), "fc");
+        assert_eq!(
+            grid.columns()
+                .map(|column| column.collect())
+                .collect::<Vec<String>>(),
+            ["ad", "be", "cf"]
+        );
+
+        let transposed = grid.transpose();
+        assert_eq!((transposed.width(), transposed.height()), (2, 3));
+        assert_eq!(transposed.to_string(), "ad\nbe\ncf\n");
+        assert_eq!(transposed[(2, 0)], grid[(0, 2)]);
+        assert_eq!(transposed.transpose(), grid);
+    }
+
+    #[test]
+    fn rotations() {
+        let grid = letters();
+        let clockwise = grid.rotate_clockwise();
+        assert_eq!(clockwise.to_string(), "da\neb\nfc\n");
+        assert_eq!(grid.rotate_counterclockwise().to_string(), "cf\nbe\nad\n");
+        assert_eq!(clockwise.rotate_counterclockwise(), grid);
+        assert_eq!(grid.rotate_counterclockwise().rotate_clockwise(), grid);
+        let turned = (0..4).fold(grid.clone(), |grid, _| grid.rotate_clockwise());
+        assert_eq!(turned, grid);
+        let turned = (0..4).fold(grid.clone(), |grid, _| grid.rotate_counterclockwise());
+        assert_eq!(turned, grid);
+    }
+
+    #[test]
+    fn no_rows() {
+        let grid = Grid::filled(3, 0, 'x');
+        assert_eq!(grid.column(2).count(), 0);
+        assert_eq!(
+            grid.columns().map(Iterator::count).collect::<Vec<_>>(),
+            [0, 0, 0]
+        );
+        let transposed = grid.transpose();
+        assert_eq!((transposed.width(), transposed.height()), (0, 3));
+        assert_eq!(grid.rotate_clockwise().height(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "Column 3 out of bounds")]
+    fn column_out_of_bounds() {
+        let _ = letters().column(3);
+    }
+
+    #[test]
+    fn neighbors_stay_in_bounds() {
+        let grid = letters();
+        let mut corner = grid.neighbors4((0, 0)).collect::<Vec<_>>();
+        corner.sort();
+        assert_eq!(corner, [(0, 1), (1, 0)]);
+        let mut edge = grid.neighbors4((1, 1)).collect::<Vec<_>>();
+        edge.sort();
+        assert_eq!(edge, [(0, 1), (1, 0), (1, 2)]);
+
+        let mut corner = grid.neighbors8((1, 2)).collect::<Vec<_>>();
+        corner.sort();
+        assert_eq!(corner, [(0, 1), (0, 2), (1, 1)]);
+        assert_eq!(grid.neighbors8((0, 1)).count(), 5);
+        assert_eq!(grid.neighbors8((5, 5)).count(), 0);
+        assert_eq!(grid.neighbors4((1, 3)).collect::<Vec<_>>(), [(1, 2)]);
+    }
+
+    #[test]
+    fn ragged_rows() {
+        let input = "header\nabc\nde\n";
+        let error = Grid::parse_within(input, &input[7..], "a letter", Some).unwrap_err();
+        assert_eq!((error.line, error.column), (3, 1));
+        assert_eq!(error.expected, "row of 3 cells");
+
+        let error = Grid::parse("ab\nabc\n", "a letter", Some).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        let error =
+            Grid::parse("ab\na?\n", "a letter", |c| c.is_alphabetic().then_some(c)).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+        assert_eq!(error.expected, "a letter");
+    }
+
+    #[test]
+    fn display() {
+        let grid = Grid::new(2, 3, vec![1, 2, 3, 4, 5, 6]);
+        assert_eq!(grid.to_string(), "12\n34\n56\n");
+        assert_eq!(
+            Grid::<u8>::parse("", "a digit", |_| None)
+                .unwrap()
+                .to_string(),
+            ""
+        );
+    }
+}
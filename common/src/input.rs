@@ -0,0 +1,72 @@
+use std::{
+    fmt::{Display, Formatter},
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+/// Where a puzzle input is read from at runtime.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    /// A file at this path.
+    File(PathBuf),
+    /// Standard input, read to its end.
+    Stdin,
+}
+
+impl Source {
+    /// Interprets a command line argument, where `-` means stdin.
+    pub fn from_arg(arg: &str) -> Source {
+        if arg == "-" {
+            Source::Stdin
+        } else {
+            Source::File(arg.into())
+        }
+    }
+
+    /// The whole input, naming the source in the error if it can't be read.
+    pub fn read(&self) -> io::Result<String> {
+        let result = match self {
+            Source::File(path) => std::fs::read_to_string(path),
+            Source::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input).map(|_| input)
+            }
+        };
+        result.map_err(|error| io::Error::new(error.kind(), format!("{}: {}", self, error)))
+    }
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Source::File(path) => write!(f, "{}", path.display()),
+            Source::Stdin => write!(f, "stdin"),
+        }
+    }
+}
+
+/// The input file of a day crate, `src/input.txt` under its directory.
+pub fn default_path(crate_dir: impl AsRef<Path>) -> PathBuf {
+    crate_dir.as_ref().join("src").join("input.txt")
+}
+
+/// Reads the input named by a command line argument, or the crate's default input file if there is
+/// none.
+///
+/// Day binaries call this with `env!("CARGO_MANIFEST_DIR")`.
+pub fn from_arg(arg: Option<&str>, crate_dir: impl AsRef<Path>) -> io::Result<String> {
+    match arg {
+        Some(arg) => Source::from_arg(arg).read(),
+        None => Source::File(default_path(crate_dir)).read(),
+    }
+}
+
+/// Reads `fixtures/<name>` under the crate directory, panicking if it is missing.
+///
+/// Meant for tests, which call this with `env!("CARGO_MANIFEST_DIR")`.
+pub fn fixture(crate_dir: impl AsRef<Path>, name: &str) -> String {
+    let path = crate_dir.as_ref().join("fixtures").join(name);
+    Source::File(path)
+        .read()
+        .unwrap_or_else(|error| panic!("Failed to read fixture: {}", error))
+}
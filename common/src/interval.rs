@@ -0,0 +1,312 @@
+use std::ops::{Range, RangeInclusive};
+
+/// An integer type that intervals can be built from.
+pub trait Discrete: Copy + Ord {
+    /// The next value, which turns an inclusive end into an exclusive one, or `None` for the
+    /// largest value.
+    fn successor(self) -> Option<Self>;
+
+    /// How many values lie from `self` up to, but not including, `end`, which must not be less,
+    /// or `None` if there are more than fit in a `u64`.
+    fn distance(self, end: Self) -> Option<u64>;
+}
+
+macro_rules! impl_discrete {
+    ($($integer:ty),*) => {
+        $(
+            impl Discrete for $integer {
+                fn successor(self) -> Option<Self> {
+                    self.checked_add(1)
+                }
+
+                fn distance(self, end: Self) -> Option<u64> {
+                    u64::try_from((end as i128).checked_sub(self as i128)?).ok()
+                }
+            }
+        )*
+    };
+}
+
+impl_discrete!(u8, u16, u32, u64, usize, i8, i16, i32, i64, i128, isize);
+
+/// The half-open interval `start..end`, which is empty unless `start < end`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Interval<T> {
+    /// The first value in the interval.
+    pub start: T,
+    /// The first value after the interval.
+    pub end: T,
+}
+
+impl<T: Discrete> Interval<T> {
+    /// The interval `start..end`.
+    pub fn new(start: T, end: T) -> Self {
+        Self { start, end }
+    }
+
+    /// The interval `first..=last`. Panics if `last` is the largest value of `T`.
+    pub fn inclusive(first: T, last: T) -> Self {
+        Self::checked_inclusive(first, last).expect("an inclusive end below the largest value")
+    }
+
+    /// The interval `first..=last`, or `None` if `last` is the largest value of `T`.
+    pub fn checked_inclusive(first: T, last: T) -> Option<Self> {
+        Some(Self::new(first, last.successor()?))
+    }
+
+    /// Whether the interval holds no value.
+    pub fn is_empty(&self) -> bool {
+        self.start >= self.end
+    }
+
+    /// The number of values in the interval, or `None` if it doesn't fit in a `u64`.
+    pub fn len(&self) -> Option<u64> {
+        if self.is_empty() {
+            Some(0)
+        } else {
+            self.start.distance(self.end)
+        }
+    }
+
+    /// Whether `value` is in the interval.
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    /// The values in both intervals, or `None` if there are none.
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let result = Self::new(self.start.max(other.start), self.end.min(other.end));
+        (!result.is_empty()).then_some(result)
+    }
+
+    /// The values in `self` but not in `other`, as up to two disjoint intervals in order.
+    pub fn difference(&self, other: &Self) -> Vec<Self> {
+        if self.is_empty() {
+            return vec![];
+        }
+        let Some(common) = self.intersection(other) else {
+            return vec![*self];
+        };
+        [
+            Self::new(self.start, common.start),
+            Self::new(common.end, self.end),
+        ]
+        .into_iter()
+        .filter(|interval| !interval.is_empty())
+        .collect()
+    }
+
+    /// Splits the interval into the values below `point` and the rest, leaving out empty halves.
+    pub fn split_at(&self, point: T) -> (Option<Self>, Option<Self>) {
+        let below = Self::new(self.start, self.end.min(point));
+        let rest = Self::new(self.start.max(point), self.end);
+        (
+            (!below.is_empty()).then_some(below),
+            (!rest.is_empty()).then_some(rest),
+        )
+    }
+
+    /// The interval as a standard range.
+    pub fn to_range(self) -> Range<T> {
+        self.start..self.end
+    }
+}
+
+impl<T: Discrete> From<Range<T>> for Interval<T> {
+    fn from(range: Range<T>) -> Self {
+        Self::new(range.start, range.end)
+    }
+}
+
+impl<T: Discrete> From<RangeInclusive<T>> for Interval<T> {
+    fn from(range: RangeInclusive<T>) -> Self {
+        let (first, last) = range.into_inner();
+        Self::inclusive(first, last)
+    }
+}
+
+/// Merges `intervals` into disjoint, non-empty intervals sorted by start. Intervals that overlap or
+/// touch become one.
+pub fn normalize<T: Discrete>(
+    intervals: impl IntoIterator<Item = Interval<T>>,
+) -> Vec<Interval<T>> {
+    let mut intervals = intervals
+        .into_iter()
+        .filter(|interval| !interval.is_empty())
+        .collect::<Vec<_>>();
+    intervals.sort_by_key(|interval| interval.start);
+    let mut result: Vec<Interval<T>> = Vec::with_capacity(intervals.len());
+    for interval in intervals {
+        match result.last_mut() {
+            Some(last) if interval.start <= last.end => last.end = last.end.max(interval.end),
+            _ => result.push(interval),
+        }
+    }
+    result
+}
+
+/// The values in either list of intervals, normalized.
+pub fn union<T: Discrete>(a: &[Interval<T>], b: &[Interval<T>]) -> Vec<Interval<T>> {
+    normalize(a.iter().chain(b).copied())
+}
+
+/// An `N`-dimensional box, the product of one interval per axis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cuboid<T, const N: usize> {
+    /// The interval spanned along every axis.
+    pub axes: [Interval<T>; N],
+}
+
+impl<T: Discrete, const N: usize> Cuboid<T, N> {
+    /// The box spanning `axes`.
+    pub fn new(axes: [Interval<T>; N]) -> Self {
+        Self { axes }
+    }
+
+    /// Whether the box holds no point, as it is empty along some axis.
+    pub fn is_empty(&self) -> bool {
+        self.axes.iter().any(Interval::is_empty)
+    }
+
+    /// The number of points in the box, or `None` if it doesn't fit in a `u64`.
+    pub fn volume(&self) -> Option<u64> {
+        if self.is_empty() {
+            return Some(0);
+        }
+        self.axes
+            .iter()
+            .try_fold(1, |volume: u64, axis| volume.checked_mul(axis.len()?))
+    }
+
+    /// Whether `point` is in the box.
+    pub fn contains(&self, point: [T; N]) -> bool {
+        self.axes
+            .iter()
+            .zip(point)
+            .all(|(interval, value)| interval.contains(value))
+    }
+
+    /// The same box with `axis` replaced by `interval`.
+    pub fn with_axis(mut self, axis: usize, interval: Interval<T>) -> Self {
+        self.axes[axis] = interval;
+        self
+    }
+
+    /// The points in both boxes, or `None` if there are none.
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let mut axes = self.axes;
+        for (axis, other) in axes.iter_mut().zip(&other.axes) {
+            *axis = axis.intersection(other)?;
+        }
+        Some(Self::new(axes))
+    }
+
+    /// The points in `self` but not in `other`, as up to `2 * N` disjoint boxes.
+    pub fn difference(&self, other: &Self) -> Vec<Self> {
+        if self.is_empty() {
+            return vec![];
+        }
+        let Some(common) = self.intersection(other) else {
+            return vec![*self];
+        };
+        // Peel off the slabs outside `common` one axis at a time, narrowing what remains to it.
+        let mut result = vec![];
+        let mut remaining = *self;
+        for axis in 0..N {
+            for slab in remaining.axes[axis].difference(&common.axes[axis]) {
+                result.push(remaining.with_axis(axis, slab));
+            }
+            remaining = remaining.with_axis(axis, common.axes[axis]);
+        }
+        result
+    }
+
+    /// Splits the box into the points whose `axis` coordinate is below `point` and the rest.
+    pub fn split_at(&self, axis: usize, point: T) -> (Option<Self>, Option<Self>) {
+        let (below, rest) = self.axes[axis].split_at(point);
+        (
+            below.map(|below| self.with_axis(axis, below)),
+            rest.map(|rest| self.with_axis(axis, rest)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn interval(range: Range<i32>) -> Interval<i32> {
+        range.into()
+    }
+
+    #[test]
+    fn interval_set_operations() {
+        let a = interval(0..10);
+        assert_eq!(a.intersection(&interval(5..15)), Some(interval(5..10)));
+        assert_eq!(a.intersection(&interval(10..15)), None);
+        assert_eq!(
+            a.difference(&interval(3..5)),
+            [interval(0..3), interval(5..10)]
+        );
+        assert_eq!(a.difference(&interval(-5..5)), [interval(5..10)]);
+        assert_eq!(a.difference(&interval(-5..15)), []);
+        assert_eq!(a.difference(&interval(20..30)), [a]);
+        assert_eq!(a.split_at(4), (Some(interval(0..4)), Some(interval(4..10))));
+        assert_eq!(a.split_at(0), (None, Some(a)));
+        assert_eq!(Interval::from(2..=4), interval(2..5));
+        assert_eq!(Interval::from(2..=4).len(), Some(3));
+        assert_eq!(Interval::new(4, 2).len(), Some(0));
+    }
+
+    #[test]
+    fn lengths_beyond_u64() {
+        assert_eq!(Interval::new(i64::MIN, i64::MAX).len(), Some(u64::MAX));
+        assert_eq!(Interval::new(i128::MIN, i128::MAX).len(), None);
+        assert_eq!(Interval::new(0, i128::from(u64::MAX) + 1).len(), None);
+        let axis = Interval::new(0, u64::MAX);
+        assert_eq!(
+            Cuboid::new([axis, Interval::new(0, 1)]).volume(),
+            Some(u64::MAX)
+        );
+        assert_eq!(Cuboid::new([axis, Interval::new(0, 2)]).volume(), None);
+        assert_eq!(Cuboid::new([axis, Interval::new(2, 2)]).volume(), Some(0));
+    }
+
+    #[test]
+    fn normalize_merges_overlapping_and_touching() {
+        let intervals = [
+            interval(8..9),
+            interval(0..3),
+            interval(5..5),
+            interval(2..4),
+            interval(4..6),
+        ];
+        assert_eq!(normalize(intervals), [interval(0..6), interval(8..9)]);
+        assert_eq!(
+            union(&[interval(0..2)], &[interval(3..4), interval(1..2)]),
+            [interval(0..2), interval(3..4)]
+        );
+    }
+
+    #[test]
+    fn cuboid_difference_is_disjoint() {
+        let outer = Cuboid::new([interval(0..4), interval(0..4), interval(0..4)]);
+        let inner = Cuboid::new([interval(1..2), interval(1..3), interval(-1..9)]);
+        let pieces = outer.difference(&inner);
+        assert_eq!(
+            pieces
+                .iter()
+                .map(|piece| piece.volume().unwrap())
+                .sum::<u64>(),
+            outer.volume().unwrap() - outer.intersection(&inner).unwrap().volume().unwrap()
+        );
+        for (index, piece) in pieces.iter().enumerate() {
+            assert!(piece.intersection(&inner).is_none());
+            for other in &pieces[index + 1..] {
+                assert!(piece.intersection(other).is_none());
+            }
+        }
+        assert!(!pieces.iter().any(|piece| piece.contains([1, 1, 0])));
+        assert!(pieces.iter().any(|piece| piece.contains([3, 1, 0])));
+    }
+}
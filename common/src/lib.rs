@@ -0,0 +1,45 @@
+//! What every day shares: the solution traits, parsing, grids and the runner plumbing.
+
+#![warn(missing_docs)]
+
+/// How answers that overflow are handled.
+pub mod arith;
+/// Answering many inputs of one day and tabulating the answers.
+pub mod batch;
+/// Finding the state of a process that repeats after many steps.
+pub mod cycle;
+/// The errors of parsing and solving.
+pub mod error;
+/// Positions on a grid and the directions between them.
+pub mod geometry;
+/// Rectangular grids of cells.
+pub mod grid;
+/// Where puzzle inputs are read from.
+pub mod input;
+/// Half-open intervals and boxes of them.
+pub mod interval;
+/// Answers as records for scripts to read.
+pub mod output;
+/// Mapping over items on several threads.
+pub mod parallel;
+/// Helpers for parsing inputs, reporting where they go wrong.
+pub mod parse;
+/// Fetching puzzle inputs and caching them.
+pub mod provider;
+/// Seeded randomness for generating inputs.
+pub mod random;
+/// Pictures of how puzzles are answered.
+pub mod render;
+/// Cheapest paths through a graph given by its successors.
+pub mod shortest_path;
+/// The solutions of days, and how their binaries run them.
+pub mod solution;
+/// Tracing the steps of simulations.
+pub mod trace;
+
+pub use error::{Error, Result};
+pub use geometry::Direction;
+pub use grid::Grid;
+pub use interval::Interval;
+pub use parse::ParseError;
+pub use solution::{Answer, Part, Solution};
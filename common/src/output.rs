@@ -0,0 +1,120 @@
+use std::{fmt::Write, time::Duration};
+
+use crate::{Answer, Part};
+
+/// The outcome of one part of one input, for output that scripts read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    /// The year of the puzzle.
+    pub year: u16,
+    /// The day of the puzzle.
+    pub day: u8,
+    /// The part answered.
+    pub part: Part,
+    /// Where the input was read from.
+    pub input: String,
+    /// The answer, or why there is none.
+    pub answer: Result<Answer, String>,
+    /// Time spent answering the part, not counting parsing.
+    pub elapsed: Duration,
+}
+
+impl Record {
+    /// `"integer"`, `"text"` or `"error"`, telling how to read the answer.
+    pub fn answer_type(&self) -> &'static str {
+        match &self.answer {
+            Ok(Answer::Integer(_)) => "integer",
+            Ok(Answer::Text(_)) => "text",
+            Err(_) => "error",
+        }
+    }
+
+    /// A JSON object on one line. Integer answers are JSON numbers, text answers and errors are
+    /// strings.
+    pub fn to_json(&self) -> String {
+        let answer = match &self.answer {
+            Ok(Answer::Integer(value)) => value.to_string(),
+            Ok(Answer::Text(text)) => json_string(text),
+            Err(message) => json_string(message),
+        };
+        format!(
+            "{{\"year\": {}, \"day\": {}, \"part\": {}, \"input\": {}, \"answer\": {}, \
+             \"type\": \"{}\", \"elapsed_ns\": {}}}",
+            self.year,
+            self.day,
+            self.part,
+            json_string(&self.input),
+            answer,
+            self.answer_type(),
+            self.elapsed.as_nanos()
+        )
+    }
+}
+
+/// The records as a JSON array with one object per line.
+pub fn to_json(records: &[Record]) -> String {
+    if records.is_empty() {
+        return "[]\n".to_string();
+    }
+    let objects = records
+        .iter()
+        .map(|record| format!("  {}", record.to_json()))
+        .collect::<Vec<_>>();
+    format!("[\n{}\n]\n", objects.join(",\n"))
+}
+
+/// `text` as a quoted JSON string.
+pub fn json_string(text: &str) -> String {
+    let mut result = String::from('"');
+    for c in text.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if c.is_control() => write!(result, "\\u{:04x}", c as u32).unwrap(),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(answer: Result<Answer, String>) -> Record {
+        Record {
+            year: 2023,
+            day: 7,
+            part: Part::Two,
+            input: "inputs/a \"b\".txt".to_string(),
+            answer,
+            elapsed: Duration::from_micros(12),
+        }
+    }
+
+    #[test]
+    fn records_to_json() {
+        let records = [
+            record(Ok(Answer::Integer(-5))),
+            record(Ok(Answer::Text("ABC".to_string()))),
+            record(Err("Parse error at line 1, column 1:\n\tx".to_string())),
+        ];
+        assert_eq!(
+            to_json(&records),
+            "[\n  \
+             {\"year\": 2023, \"day\": 7, \"part\": 2, \"input\": \"inputs/a \\\"b\\\".txt\", \
+             \"answer\": -5, \"type\": \"integer\", \"elapsed_ns\": 12000},\n  \
+             {\"year\": 2023, \"day\": 7, \"part\": 2, \"input\": \"inputs/a \\\"b\\\".txt\", \
+             \"answer\": \"ABC\", \"type\": \"text\", \"elapsed_ns\": 12000},\n  \
+             {\"year\": 2023, \"day\": 7, \"part\": 2, \"input\": \"inputs/a \\\"b\\\".txt\", \
+             \"answer\": \"Parse error at line 1, column 1:\\n\\tx\", \"type\": \"error\", \
+             \"elapsed_ns\": 12000}\n]\n"
+        );
+        assert_eq!(to_json(&[]), "[]\n");
+        assert_eq!(json_string("\u{1}"), "\"\\u0001\"");
+    }
+}
@@ -0,0 +1,79 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// How many threads [`map`] may use; 0 means one per available core.
+static THREADS: AtomicUsize = AtomicUsize::new(0);
+
+/// Limits [`map`] to `threads` threads, or lifts the limit with 0. With 1 it takes the serial path,
+/// which is how to measure the speedup of a build with the `parallel` feature.
+pub fn set_threads(threads: usize) {
+    THREADS.store(threads, Ordering::Relaxed);
+}
+
+/// How many threads [`map`] uses: always 1 without the `parallel` feature.
+pub fn threads() -> usize {
+    if !cfg!(feature = "parallel") {
+        return 1;
+    }
+    match THREADS.load(Ordering::Relaxed) {
+        0 => std::thread::available_parallelism().map_or(1, usize::from),
+        threads => threads,
+    }
+}
+
+/// Applies `f` to every one of `items`, spread over [`threads`] scoped threads that each take the
+/// next item not yet taken. The results are in the order of `items` however the work was spread, so
+/// answers don't depend on the number of threads.
+pub fn map<T: Sync, R: Send>(items: &[T], f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    let threads = threads().min(items.len());
+    if threads <= 1 {
+        return items.iter().map(f).collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let mut results = (0..items.len()).map(|_| None).collect::<Vec<_>>();
+    std::thread::scope(|scope| {
+        let workers = (0..threads)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = vec![];
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(item) = items.get(index) else {
+                            break done;
+                        };
+                        done.push((index, f(item)));
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        for worker in workers {
+            let done = worker
+                .join()
+                .unwrap_or_else(|payload| std::panic::resume_unwind(payload));
+            for (index, result) in done {
+                results[index] = Some(result);
+            }
+        }
+    });
+    results
+        .into_iter()
+        .map(|result| result.expect("every item is mapped"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn results_in_order() {
+        let items = (0..1000u64).collect::<Vec<_>>();
+        let expected = items.iter().map(|item| item * item).collect::<Vec<_>>();
+        for threads in [0, 1, 3] {
+            set_threads(threads);
+            assert_eq!(map(&items, |item| item * item), expected);
+        }
+        assert_eq!(map(&[] as &[u64], |item| *item), []);
+        set_threads(0);
+    }
+}
@@ -0,0 +1,268 @@
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+/// Longest snippet of offending input kept in a [`ParseError`].
+const MAX_SNIPPET_LEN: usize = 20;
+
+/// Where and why the input couldn't be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line number, or 0 if unknown.
+    pub line: usize,
+    /// 1-based column, in characters, or 0 if unknown.
+    pub column: usize,
+    /// The input from the error position, up to the end of its line. Empty at the end of a line.
+    pub snippet: String,
+    /// What the parser expected to find there instead.
+    pub expected: String,
+}
+
+impl ParseError {
+    /// An error at the start of `token`, a slice of `input`.
+    ///
+    /// Parsers slice their tokens out of the input anyway, so the location comes for free. Pass an
+    /// empty slice at the end of a line when something is missing. A `token` from elsewhere is
+    /// reported at line 0, column 0.
+    pub fn at(input: &str, token: &str, expected: impl Into<String>) -> ParseError {
+        let offset = (token.as_ptr() as usize)
+            .checked_sub(input.as_ptr() as usize)
+            .filter(|&offset| offset <= input.len() && token.len() <= input.len() - offset)
+            .filter(|&offset| input.is_char_boundary(offset));
+        let Some(offset) = offset else {
+            return ParseError {
+                line: 0,
+                column: 0,
+                snippet: snippet(token),
+                expected: expected.into(),
+            };
+        };
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+        ParseError {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            snippet: snippet(&input[offset..]),
+            expected: expected.into(),
+        }
+    }
+}
+
+/// The start of `rest` up to the end of its line, cut to [`MAX_SNIPPET_LEN`] characters.
+fn snippet(rest: &str) -> String {
+    rest.lines()
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .take(MAX_SNIPPET_LEN)
+        .collect()
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, found ",
+            self.line, self.column, self.expected
+        )?;
+        if self.snippet.is_empty() {
+            write!(f, "end of line")
+        } else {
+            write!(f, "{:?}", self.snippet)
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses `token`, a slice of `input`, as a number.
+pub fn number<T: FromStr>(input: &str, token: &str) -> Result<T, ParseError> {
+    token
+        .parse()
+        .map_err(|_| ParseError::at(input, token, "number"))
+}
+
+/// Returns the next item of `tokens`, or an error at the end of `line` if there is none.
+pub fn next<'a>(
+    input: &str,
+    line: &'a str,
+    tokens: &mut impl Iterator<Item = &'a str>,
+    expected: &str,
+) -> Result<&'a str, ParseError> {
+    tokens
+        .next()
+        .ok_or_else(|| ParseError::at(input, &line[line.len()..], expected))
+}
+
+/// Strips `prefix` from `token`, a slice of `input`.
+pub fn strip_prefix<'a>(input: &str, token: &'a str, prefix: &str) -> Result<&'a str, ParseError> {
+    token
+        .strip_prefix(prefix)
+        .ok_or_else(|| ParseError::at(input, token, format!("{:?}", prefix)))
+}
+
+/// Splits `token`, a slice of `input`, once at `delimiter`.
+pub fn split_once<'a>(
+    input: &str,
+    token: &'a str,
+    delimiter: &str,
+) -> Result<(&'a str, &'a str), ParseError> {
+    token
+        .split_once(delimiter)
+        .ok_or_else(|| ParseError::at(input, &token[token.len()..], format!("{:?}", delimiter)))
+}
+
+/// Strips `suffix` from `token`, a slice of `input`.
+pub fn strip_suffix<'a>(input: &str, token: &'a str, suffix: &str) -> Result<&'a str, ParseError> {
+    token
+        .strip_suffix(suffix)
+        .ok_or_else(|| ParseError::at(input, &token[token.len()..], format!("{:?}", suffix)))
+}
+
+/// The inside of `token`, a slice of `input` that must start with `open` and end with `close`,
+/// like "(BBB, CCC)".
+pub fn delimited<'a>(
+    input: &str,
+    token: &'a str,
+    open: &str,
+    close: &str,
+) -> Result<&'a str, ParseError> {
+    strip_suffix(input, strip_prefix(input, token, open)?, close)
+}
+
+/// Parses each of the items of `token` separated by `separator`.
+pub fn separated<'a, T>(
+    token: &'a str,
+    separator: &str,
+    item: impl FnMut(&'a str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    token.split(separator).map(item).collect()
+}
+
+/// Parses exactly `N` items of `token`, a slice of `input`, separated by `separator`, like the
+/// coordinates in "1,0,1". Extra separators end up in the last item.
+pub fn separated_n<'a, T, const N: usize>(
+    input: &str,
+    token: &'a str,
+    separator: &str,
+    mut item: impl FnMut(&'a str) -> Result<T, ParseError>,
+) -> Result<[T; N], ParseError> {
+    let expected = format!("{:?}", separator);
+    let mut pieces = token.splitn(N, separator);
+    let items = (0..N)
+        .map(|_| item(next(input, token, &mut pieces, &expected)?))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(items
+        .try_into()
+        .unwrap_or_else(|_| unreachable!("there are N items")))
+}
+
+/// Parses the whitespace separated numbers in `token`, a slice of `input`.
+pub fn numbers<T: FromStr>(input: &str, token: &str) -> Result<Vec<T>, ParseError> {
+    token
+        .split_whitespace()
+        .map(|number| self::number(input, number))
+        .collect()
+}
+
+/// Parses a record like "x=787,m=2655": fields separated by `separator`, each a key and a value
+/// around `assign`.
+pub fn record<'a, T>(
+    input: &str,
+    token: &'a str,
+    separator: &str,
+    assign: &str,
+    mut field: impl FnMut(&'a str, &'a str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    separated(token, separator, |pair| {
+        let (key, value) = split_once(input, pair, assign)?;
+        field(key, value)
+    })
+}
+
+/// Splits `token`, a slice of `input`, into exactly `N` sections of lines separated by blank lines.
+/// Blank lines before the first section and after the last are ignored.
+pub fn sections<'a, const N: usize>(
+    input: &str,
+    token: &'a str,
+) -> Result<[&'a str; N], ParseError> {
+    let mut sections = Vec::new();
+    let mut section = None;
+    let mut offset = 0;
+    for line in token.split_inclusive('\n') {
+        let content = line.trim_end_matches(['\n', '\r']);
+        if content.trim().is_empty() {
+            sections.extend(section.take().map(|(start, end)| &token[start..end]));
+        } else {
+            let start = section.map_or(offset, |(start, _)| start);
+            section = Some((start, offset + content.len()));
+        }
+        offset += line.len();
+    }
+    sections.extend(section.map(|(start, end)| &token[start..end]));
+    if let Some(extra) = sections.get(N) {
+        return Err(ParseError::at(input, extra, "end of input"));
+    }
+    sections.try_into().map_err(|_| {
+        let expected = format!("{} sections separated by blank lines", N);
+        ParseError::at(input, &token[token.len()..], expected)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn combinators() {
+        let input = "a=1, b=22\n(3,4,5)\n";
+        let (first, second) = split_once(input, input.trim_end(), "\n").unwrap();
+        let fields = record(input, first, ", ", "=", |key, value| {
+            Ok((key, number::<u8>(input, value)?))
+        });
+        assert_eq!(fields, Ok(vec![("a", 1), ("b", 22)]));
+
+        let inside = delimited(input, second, "(", ")").unwrap();
+        let coordinates = separated_n(input, inside, ",", |token| number::<u8>(input, token));
+        assert_eq!(coordinates, Ok([3, 4, 5]));
+        let error = separated_n::<_, 2>(input, inside, ",", |token| number::<u8>(input, token));
+        assert_eq!(
+            error.unwrap_err().to_string(),
+            "line 2, column 4: expected number, found \"4,5)\""
+        );
+        let error = separated_n::<_, 4>(input, inside, ",", |token| number::<u8>(input, token));
+        assert_eq!(
+            error.unwrap_err().to_string(),
+            "line 2, column 7: expected \",\", found \")\""
+        );
+        assert_eq!(
+            separated(inside, ",", |token| number::<u8>(input, token)),
+            Ok(vec![3, 4, 5])
+        );
+    }
+
+    #[test]
+    fn errors_outside_the_input() {
+        let input = "a=1\n";
+        let error = ParseError::at(input, "b=2\nc", "a");
+        assert_eq!(
+            (error.line, error.column, error.snippet.as_str()),
+            (0, 0, "b=2")
+        );
+        let error = ParseError::at(&input[..2], &input[1..], "a");
+        assert_eq!((error.line, error.column), (0, 0));
+        let error = ParseError::at(input, &input[4..], "a");
+        assert_eq!((error.line, error.column), (2, 1));
+    }
+
+    #[test]
+    fn blank_line_sections() {
+        let input = "\na\nb\n\n\r\nc\n\n";
+        assert_eq!(sections(input, input), Ok(["a\nb", "c"]));
+        let error = sections::<3>(input, input).unwrap_err();
+        assert_eq!((error.line, error.column), (8, 1));
+        let error = sections::<1>(input, input).unwrap_err();
+        assert_eq!((error.line, error.expected.as_str()), (6, "end of input"));
+    }
+}
@@ -0,0 +1,308 @@
+use std::{
+    fs,
+    io::{self, Read, Write},
+    net::TcpStream,
+    path::PathBuf,
+    process::{Command, Stdio},
+};
+
+use crate::input::Source;
+
+/// Where puzzle inputs are fetched from unless `AOC_ENDPOINT` says otherwise.
+pub const DEFAULT_ENDPOINT: &str = "https://adventofcode.com/{year}/day/{day}/input";
+
+const USER_AGENT: &str = "github.com/chubei/advent_of_code input provider";
+
+/// Fetches the body of a URL, sending the session token as the `session` cookie.
+pub trait Backend {
+    /// The body of `url`, or an error if it can't be fetched.
+    fn get(&self, url: &str, session: &str) -> io::Result<String>;
+}
+
+/// A minimal HTTP/1.0 client on a plain TCP stream, for `http://` URLs only.
+pub struct Http;
+
+impl Backend for Http {
+    fn get(&self, url: &str, session: &str) -> io::Result<String> {
+        let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, message);
+        let rest = url
+            .strip_prefix("http://")
+            .ok_or_else(|| invalid(format!("{} is not an http:// URL", url)))?;
+        let (host, path) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+        let path = if path.is_empty() { "/" } else { path };
+        let address = if host.contains(':') {
+            host.to_string()
+        } else {
+            format!("{}:80", host)
+        };
+
+        // HTTP/1.0, so that the body is neither chunked nor followed by another response.
+        let mut stream = TcpStream::connect(address)?;
+        write!(
+            stream,
+            "GET {} HTTP/1.0\r\nHost: {}\r\nCookie: session={}\r\nUser-Agent: {}\r\n\r\n",
+            path, host, session, USER_AGENT
+        )?;
+        let mut response = Vec::new();
+        stream.read_to_end(&mut response)?;
+        let response = String::from_utf8(response)
+            .map_err(|_| invalid(format!("{} answered with invalid UTF-8", url)))?;
+
+        let (head, body) = response
+            .split_once("\r\n\r\n")
+            .ok_or_else(|| invalid(format!("{} answered with a malformed response", url)))?;
+        let status = head.lines().next().unwrap_or_default();
+        if status.split_whitespace().nth(1) != Some("200") {
+            return Err(io::Error::other(format!("{} answered {:?}", url, status)));
+        }
+        Ok(body.to_string())
+    }
+}
+
+/// Runs `curl`, for `https://` URLs like the real endpoint's.
+pub struct Curl;
+
+impl Backend for Curl {
+    fn get(&self, url: &str, session: &str) -> io::Result<String> {
+        // The options go through stdin, keeping the token out of the process list.
+        let mut child = Command::new("curl")
+            .args(["--silent", "--show-error", "--fail", "--config", "-"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        let config = format!(
+            "url = {}\ncookie = {}\nuser-agent = {}\n",
+            config_string(url),
+            config_string(&format!("session={}", session)),
+            config_string(USER_AGENT)
+        );
+        child
+            .stdin
+            .take()
+            .expect("stdin is piped")
+            .write_all(config.as_bytes())?;
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            let error = String::from_utf8_lossy(&output.stderr);
+            return Err(io::Error::other(format!("curl failed: {}", error.trim())));
+        }
+        String::from_utf8(output.stdout).map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{} answered with invalid UTF-8", url),
+            )
+        })
+    }
+}
+
+/// `value` as a quoted string of curl's config syntax, so that it can't end its option early or
+/// start another one.
+fn config_string(value: &str) -> String {
+    let mut quoted = String::from('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Finds the input of a day in a local cache, fetching it into the cache the first time.
+pub struct Provider {
+    /// Inputs are cached as `<year>/day_<day>.txt` under it.
+    pub cache_dir: PathBuf,
+    /// The URL of an input, with `{year}` and `{day}` standing for the day's.
+    pub endpoint: String,
+    /// Without a token, only cached inputs can be provided.
+    pub session: Option<String>,
+    /// Fetches the inputs that aren't cached yet.
+    pub backend: Box<dyn Backend>,
+}
+
+impl Provider {
+    /// A provider fetching from `endpoint` with [`Http`] or [`Curl`], depending on its scheme.
+    pub fn new(cache_dir: PathBuf, endpoint: String, session: Option<String>) -> Provider {
+        let backend: Box<dyn Backend> = if endpoint.starts_with("http://") {
+            Box::new(Http)
+        } else {
+            Box::new(Curl)
+        };
+        Provider {
+            cache_dir,
+            endpoint,
+            session,
+            backend,
+        }
+    }
+
+    /// Configured by the environment variables `AOC_CACHE` (or else `default_cache_dir`),
+    /// `AOC_ENDPOINT` (or else [`DEFAULT_ENDPOINT`]) and `AOC_SESSION`.
+    pub fn from_env(default_cache_dir: PathBuf) -> Provider {
+        let cache_dir = std::env::var_os("AOC_CACHE").map_or(default_cache_dir, PathBuf::from);
+        let endpoint =
+            std::env::var("AOC_ENDPOINT").unwrap_or_else(|_| DEFAULT_ENDPOINT.to_string());
+        let session = std::env::var("AOC_SESSION")
+            .ok()
+            .map(|session| session.trim().to_string())
+            .filter(|session| !session.is_empty());
+        Provider::new(cache_dir, endpoint, session)
+    }
+
+    /// Where the input of the day is cached.
+    pub fn cache_path(&self, year: u16, day: u8) -> PathBuf {
+        self.cache_dir
+            .join(year.to_string())
+            .join(format!("day_{}.txt", day))
+    }
+
+    /// The URL the input of the day is fetched from.
+    pub fn url(&self, year: u16, day: u8) -> String {
+        self.endpoint
+            .replace("{year}", &year.to_string())
+            .replace("{day}", &day.to_string())
+    }
+
+    /// The path of the cached input of the day, fetched first if it isn't cached yet. A cached
+    /// input is never fetched again.
+    pub fn fetch(&self, year: u16, day: u8) -> io::Result<PathBuf> {
+        let path = self.cache_path(year, day);
+        if path.is_file() {
+            return Ok(path);
+        }
+        let session = self.session.as_deref().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!(
+                    "{} is not cached, and AOC_SESSION is not set to fetch it",
+                    path.display()
+                ),
+            )
+        })?;
+        // The token goes into a header, which a control character would end or split.
+        if session.chars().any(char::is_control) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "AOC_SESSION contains control characters",
+            ));
+        }
+        let input = self.backend.get(&self.url(year, day), session)?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        // Renamed into place, so that an interrupted write can't pass for a cached input.
+        let partial = path.with_extension("partial");
+        fs::write(&partial, input)?;
+        fs::rename(&partial, &path)?;
+        Ok(path)
+    }
+
+    /// Reads the input of the day, fetching it first if it isn't cached yet.
+    pub fn read(&self, year: u16, day: u8) -> io::Result<String> {
+        Source::File(self.fetch(year, day)?).read()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        net::TcpListener,
+        sync::{Arc, Mutex},
+        thread,
+    };
+
+    use super::*;
+
+    /// Serves "input of <path>" for every path but `/missing`, recording each request.
+    fn stub_server() -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let requests = Arc::new(Mutex::new(vec![]));
+        let recorded = requests.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut request = Vec::new();
+                let mut buffer = [0; 1024];
+                while !request.ends_with(b"\r\n\r\n") {
+                    let read = stream.read(&mut buffer).unwrap();
+                    if read == 0 {
+                        break;
+                    }
+                    request.extend_from_slice(&buffer[..read]);
+                }
+                let request = String::from_utf8(request).unwrap();
+                let path = request.split_whitespace().nth(1).unwrap_or_default();
+                let response = match path {
+                    "/missing" => "HTTP/1.0 404 Not Found\r\n\r\n".to_string(),
+                    path => format!("HTTP/1.0 200 OK\r\n\r\ninput of {}\n", path),
+                };
+                stream.write_all(response.as_bytes()).unwrap();
+                recorded.lock().unwrap().push(request);
+            }
+        });
+        (format!("http://{}", address), requests)
+    }
+
+    fn cache_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn fetches_once_then_reads_the_cache() {
+        let (server, requests) = stub_server();
+        let dir = cache_dir("provider_fetch");
+        let endpoint = format!("{}/{{year}}/day/{{day}}/input", server);
+        let provider = Provider::new(dir.clone(), endpoint, Some("token".to_string()));
+
+        let expected = "input of /2023/day/5/input\n";
+        assert_eq!(provider.read(2023, 5).unwrap(), expected);
+        assert_eq!(provider.read(2023, 5).unwrap(), expected);
+        let cached = fs::read_to_string(dir.join("2023").join("day_5.txt")).unwrap();
+        assert_eq!(cached, expected);
+        let requests = requests.lock().unwrap().clone();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].starts_with("GET /2023/day/5/input HTTP/1.0\r\n"));
+        assert!(requests[0].contains("\r\nCookie: session=token\r\n"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn failures_cache_nothing() {
+        let (server, requests) = stub_server();
+        let dir = cache_dir("provider_failures");
+        let endpoint = format!("{}/missing", server);
+        let mut provider = Provider::new(dir.clone(), endpoint, None);
+
+        let error = provider.read(2023, 1).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::NotFound);
+        assert!(requests.lock().unwrap().is_empty());
+
+        provider.session = Some("token".to_string());
+        let error = provider.read(2023, 1).unwrap_err();
+        assert!(error.to_string().contains("404 Not Found"), "{}", error);
+        assert!(!provider.cache_path(2023, 1).exists());
+        assert_eq!(requests.lock().unwrap().len(), 1);
+
+        provider.session = Some("token\r\nX-Injected: 1".to_string());
+        let error = provider.read(2023, 1).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        assert_eq!(requests.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn curl_config_strings_are_escaped() {
+        assert_eq!(
+            config_string("a\"b\\c\nurl = \"x\""),
+            r#""a\"b\\c\nurl = \"x\"""#
+        );
+    }
+}
@@ -0,0 +1,96 @@
+use std::{fmt::Debug, ops::Range};
+
+/// A small seeded pseudo-random number generator (SplitMix64), so that a generated input can be
+/// reproduced from its seed. Not for anything that needs real randomness.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// A generator that always produces the same numbers from `seed`.
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// The next number, uniformly distributed over all of `u64`.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `range`, which must not be empty. Slightly biased towards small numbers for
+    /// huge ranges, which doesn't matter for test inputs.
+    pub fn range(&mut self, range: Range<u64>) -> u64 {
+        assert!(!range.is_empty(), "empty range {:?}", range);
+        range.start + self.next_u64() % (range.end - range.start)
+    }
+
+    /// An index into something of length `len`, which must not be 0.
+    pub fn index(&mut self, len: usize) -> usize {
+        self.range(0..len as u64) as usize
+    }
+
+    /// True with probability `numerator / denominator`.
+    pub fn chance(&mut self, numerator: u64, denominator: u64) -> bool {
+        self.range(0..denominator) < numerator
+    }
+
+    /// A random item of `items`, which must not be empty.
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.index(items.len())]
+    }
+
+    /// Puts `items` in a random order.
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for index in (1..items.len()).rev() {
+            items.swap(index, self.index(index + 1));
+        }
+    }
+}
+
+/// Compares `solve` with `reference` on the inputs `generate` makes from seeds `0..cases`,
+/// panicking with the seed and input of the first disagreement.
+pub fn assert_agree<T: PartialEq + Debug>(
+    cases: u64,
+    mut generate: impl FnMut(&mut Rng) -> String,
+    mut solve: impl FnMut(&str) -> T,
+    mut reference: impl FnMut(&str) -> T,
+) {
+    for seed in 0..cases {
+        let input = generate(&mut Rng::new(seed));
+        let (actual, expected) = (solve(&input), reference(&input));
+        assert!(
+            actual == expected,
+            "seed {}: got {:?}, reference gives {:?} for input:\n{}",
+            seed,
+            actual,
+            expected,
+            input
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reproducible_and_in_range() {
+        let numbers = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..100).map(|_| rng.range(10..20)).collect::<Vec<_>>()
+        };
+        assert_eq!(numbers(1), numbers(1));
+        assert_ne!(numbers(1), numbers(2));
+        assert!(numbers(1).iter().all(|n| (10..20).contains(n)));
+
+        let mut items = (0..10).collect::<Vec<_>>();
+        Rng::new(3).shuffle(&mut items);
+        items.sort();
+        assert_eq!(items, (0..10).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,279 @@
+use std::fmt::Write;
+
+use crate::{geometry::Position, Grid};
+
+/// A color to highlight cells with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Color {
+    /// Red.
+    Red,
+    /// Green.
+    Green,
+    /// Yellow.
+    Yellow,
+    /// Blue.
+    Blue,
+    /// Magenta.
+    Magenta,
+    /// Cyan.
+    Cyan,
+}
+
+impl Color {
+    /// The SGR parameter setting this color as the background.
+    fn ansi_background(self) -> u8 {
+        match self {
+            Color::Red => 41,
+            Color::Green => 42,
+            Color::Yellow => 43,
+            Color::Blue => 44,
+            Color::Magenta => 45,
+            Color::Cyan => 46,
+        }
+    }
+
+    fn svg(self) -> &'static str {
+        match self {
+            Color::Red => "#e06c75",
+            Color::Green => "#98c379",
+            Color::Yellow => "#e5c07b",
+            Color::Blue => "#61afef",
+            Color::Magenta => "#c678dd",
+            Color::Cyan => "#56b6c2",
+        }
+    }
+}
+
+/// Named cells to highlight in one color.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Overlay {
+    /// What the cells are, shown in the legend.
+    pub name: String,
+    /// The background of the cells.
+    pub color: Color,
+    /// The cells to highlight.
+    pub cells: Vec<Position>,
+}
+
+/// How to output a [`Picture`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Text with ANSI escape codes, for a terminal.
+    Ansi,
+    /// An SVG document, for a browser.
+    Svg,
+}
+
+impl Format {
+    /// The format named `name` on the command line.
+    pub fn from_name(name: &str) -> Option<Format> {
+        match name {
+            "ansi" => Some(Format::Ansi),
+            "svg" => Some(Format::Svg),
+            _ => None,
+        }
+    }
+}
+
+/// A grid of characters with overlays on top. Where overlays cover the same cell, the last one
+/// added shows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Picture {
+    /// The characters of the picture.
+    pub cells: Grid<char>,
+    /// The overlays, the first at the bottom.
+    pub overlays: Vec<Overlay>,
+}
+
+/// Width and height of a cell in an SVG, in pixels.
+const SVG_CELL_SIZE: usize = 12;
+
+impl Picture {
+    /// A picture of `cells` with no overlays.
+    pub fn new(cells: Grid<char>) -> Self {
+        Self {
+            cells,
+            overlays: vec![],
+        }
+    }
+
+    /// Adds an overlay on top of the existing ones. Cells outside the grid are ignored.
+    pub fn with_overlay(
+        mut self,
+        name: &str,
+        color: Color,
+        cells: impl IntoIterator<Item = Position>,
+    ) -> Self {
+        self.overlays.push(Overlay {
+            name: name.to_string(),
+            color,
+            cells: cells.into_iter().collect(),
+        });
+        self
+    }
+
+    /// The index of the overlay every cell shows, if any.
+    fn shown(&self) -> Grid<Option<usize>> {
+        let mut shown = self.cells.map(|_| None);
+        for (index, overlay) in self.overlays.iter().enumerate() {
+            for &position in &overlay.cells {
+                if let Some(cell) = shown.get_mut(position) {
+                    *cell = Some(index);
+                }
+            }
+        }
+        shown
+    }
+
+    /// The picture in `format`.
+    pub fn render(&self, format: Format) -> String {
+        match format {
+            Format::Ansi => self.to_ansi(),
+            Format::Svg => self.to_svg(),
+        }
+    }
+
+    /// One line per row, followed by a legend line naming the overlays.
+    pub fn to_ansi(&self) -> String {
+        let colors = self
+            .shown()
+            .map(|&index| index.map(|index| self.overlays[index].color));
+        let mut result = String::new();
+        for (row, colors) in self.cells.rows().zip(colors.rows()) {
+            let mut current = None;
+            for (&c, &color) in row.iter().zip(colors) {
+                if color != current {
+                    match color {
+                        Some(color) => write!(result, "\x1b[{};30m", color.ansi_background()),
+                        None => write!(result, "\x1b[0m"),
+                    }
+                    .unwrap();
+                    current = color;
+                }
+                result.push(c);
+            }
+            if current.is_some() {
+                result.push_str("\x1b[0m");
+            }
+            result.push('\n');
+        }
+        let legend = self
+            .overlays
+            .iter()
+            .map(|overlay| {
+                format!(
+                    "\x1b[{};30m \x1b[0m {}",
+                    overlay.color.ansi_background(),
+                    overlay.name
+                )
+            })
+            .collect::<Vec<_>>();
+        if !legend.is_empty() {
+            writeln!(result, "{}", legend.join("  ")).unwrap();
+        }
+        result
+    }
+
+    /// A standalone SVG document, with one group of rectangles per overlay under the text.
+    pub fn to_svg(&self) -> String {
+        let size = SVG_CELL_SIZE;
+        let (width, height) = (self.cells.width() * size, self.cells.height() * size);
+        let mut result = String::new();
+        writeln!(
+            result,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" \
+             viewBox=\"0 0 {} {}\" font-family=\"monospace\" font-size=\"{}\">",
+            width, height, width, height, size
+        )
+        .unwrap();
+        writeln!(
+            result,
+            "<rect width=\"100%\" height=\"100%\" fill=\"white\"/>"
+        )
+        .unwrap();
+        let shown = self.shown();
+        for (index, overlay) in self.overlays.iter().enumerate() {
+            writeln!(
+                result,
+                "<g fill=\"{}\"><title>{}</title>",
+                overlay.color.svg(),
+                escape(&overlay.name)
+            )
+            .unwrap();
+            // Only the cells where this overlay shows, so that later overlays stay on top.
+            for ((row, col), _) in shown
+                .enumerate()
+                .filter(|&(_, &shown)| shown == Some(index))
+            {
+                writeln!(
+                    result,
+                    "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"/>",
+                    col * size,
+                    row * size,
+                    size,
+                    size
+                )
+                .unwrap();
+            }
+            writeln!(result, "</g>").unwrap();
+        }
+        writeln!(
+            result,
+            "<g text-anchor=\"middle\" dominant-baseline=\"central\">"
+        )
+        .unwrap();
+        for ((row, col), &c) in self.cells.enumerate() {
+            if c == ' ' {
+                continue;
+            }
+            writeln!(
+                result,
+                "<text x=\"{}\" y=\"{}\">{}</text>",
+                col * size + size / 2,
+                row * size + size / 2,
+                escape(&c.to_string())
+            )
+            .unwrap();
+        }
+        writeln!(result, "</g>\n</svg>").unwrap();
+        result
+    }
+}
+
+/// Escapes the characters that are special in SVG text.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn picture() -> Picture {
+        Picture::new(Grid::new(3, 2, "a<c.ef".chars().collect()))
+            .with_overlay("first", Color::Red, [(0, 0), (0, 1), (5, 5)])
+            .with_overlay("second", Color::Blue, [(0, 1)])
+    }
+
+    #[test]
+    fn ansi_colors_runs_of_cells() {
+        assert_eq!(
+            picture().to_ansi(),
+            "\x1b[41;30ma\x1b[44;30m<\x1b[0mc\n.ef\n\
+             \x1b[41;30m \x1b[0m first  \x1b[44;30m \x1b[0m second\n"
+        );
+    }
+
+    #[test]
+    fn svg_draws_visible_overlay_cells_and_escapes_text() {
+        let svg = picture().to_svg();
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.contains("width=\"36\" height=\"24\""));
+        assert_eq!(svg.matches("<rect x=").count(), 2);
+        assert!(svg.contains("<rect x=\"12\" y=\"0\" width=\"12\" height=\"12\"/>"));
+        assert!(svg.contains(">&lt;</text>"));
+        assert_eq!(svg.matches("<text ").count(), 6);
+    }
+}
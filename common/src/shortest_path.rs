@@ -0,0 +1,175 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+    hash::Hash,
+    ops::Add,
+};
+
+/// A cheapest path, from one of the starts to a goal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<S, C> {
+    /// The total cost of the steps of the path.
+    pub cost: C,
+    /// Every state on the path, the start first and the goal last.
+    pub states: Vec<S>,
+}
+
+/// Finds a cheapest path from any of `starts` to a state that `is_goal`, where `neighbors` lists
+/// the states one move away along with the cost of the move. Costs must not be negative, and
+/// `C::default()` must be zero.
+pub fn dijkstra<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    neighbors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(starts, neighbors, |_| C::default(), is_goal)
+}
+
+/// Like [`dijkstra`], but explores states in order of their cost plus `heuristic`, which must never
+/// overestimate the remaining cost to a goal.
+pub fn astar<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut neighbors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (S, C)>,
+{
+    // States are numbered in the order they are found, and referred to by number from then on.
+    let mut indices = HashMap::new();
+    let mut states = Vec::new();
+    // The cheapest known cost of every state and the state it was reached from.
+    let mut costs: Vec<C> = Vec::new();
+    let mut parents = Vec::new();
+    let mut queue = BinaryHeap::new();
+
+    for start in starts {
+        if indices.contains_key(&start) {
+            continue;
+        }
+        let index = states.len();
+        indices.insert(start.clone(), index);
+        queue.push(Reverse((heuristic(&start), C::default(), index)));
+        states.push(start);
+        costs.push(C::default());
+        parents.push(None);
+    }
+
+    while let Some(Reverse((_, cost, index))) = queue.pop() {
+        if cost > costs[index] {
+            // Already reached more cheaply.
+            continue;
+        }
+        if is_goal(&states[index]) {
+            let mut path = vec![index];
+            while let Some(parent) = parents[*path.last().unwrap()] {
+                path.push(parent);
+            }
+            return Some(Path {
+                cost,
+                states: path
+                    .into_iter()
+                    .rev()
+                    .map(|index| states[index].clone())
+                    .collect(),
+            });
+        }
+        for (neighbor, step_cost) in neighbors(&states[index]) {
+            let neighbor_cost = cost + step_cost;
+            let neighbor_index = match indices.get(&neighbor) {
+                Some(&neighbor_index) => {
+                    if neighbor_cost >= costs[neighbor_index] {
+                        continue;
+                    }
+                    costs[neighbor_index] = neighbor_cost;
+                    parents[neighbor_index] = Some(index);
+                    neighbor_index
+                }
+                None => {
+                    let neighbor_index = states.len();
+                    indices.insert(neighbor.clone(), neighbor_index);
+                    states.push(neighbor);
+                    costs.push(neighbor_cost);
+                    parents.push(Some(index));
+                    neighbor_index
+                }
+            };
+            let estimate = neighbor_cost + heuristic(&states[neighbor_index]);
+            queue.push(Reverse((estimate, neighbor_cost, neighbor_index)));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Moves of +1 cost 1 and moves of *2 cost 3.
+    fn neighbors(&state: &u32) -> [(u32, u32); 2] {
+        [(state + 1, 1), (state * 2, 3)]
+    }
+
+    /// The cheapest cost from 1 to every number up to `max`, as states only ever grow.
+    fn expected_costs(max: u32) -> Vec<u32> {
+        let mut costs = vec![0; max as usize + 1];
+        for n in 2..=max as usize {
+            costs[n] = costs[n - 1] + 1;
+            if n % 2 == 0 {
+                costs[n] = costs[n].min(costs[n / 2] + 3);
+            }
+        }
+        costs
+    }
+
+    #[test]
+    fn dijkstra_finds_cheapest_path() {
+        let expected = expected_costs(50);
+        for goal in 1..=50 {
+            let path = dijkstra([1], neighbors, |&state| state == goal).unwrap();
+            assert_eq!(path.cost, expected[goal as usize], "{}", goal);
+            assert_eq!(path.states.first(), Some(&1));
+            assert_eq!(path.states.last(), Some(&goal));
+            let cost = path
+                .states
+                .windows(2)
+                .map(|step| if step[1] == step[0] + 1 { 1 } else { 3 })
+                .sum::<u32>();
+            assert_eq!(cost, path.cost);
+        }
+    }
+
+    #[test]
+    fn astar_agrees_with_dijkstra() {
+        let expected = expected_costs(50);
+        for goal in 1..=50 {
+            // Any state short of the goal is at least one move away.
+            let path = astar(
+                [1],
+                |&state| {
+                    neighbors(&state)
+                        .into_iter()
+                        .filter(move |&(next, _)| next <= goal)
+                },
+                |&state| u32::from(state < goal),
+                |&state| state == goal,
+            )
+            .unwrap();
+            assert_eq!(path.cost, expected[goal as usize], "{}", goal);
+        }
+    }
+
+    #[test]
+    fn unreachable_goal() {
+        let neighbors = |&state: &u32| (state < 10).then_some((state + 1, 1u32));
+        assert_eq!(dijkstra([1], neighbors, |&state| state == 20), None);
+    }
+}
@@ -0,0 +1,422 @@
+use std::{
+    fmt::{Display, Formatter},
+    fs::File,
+    io::{self, BufWriter, Write},
+    marker::PhantomData,
+    path::Path,
+    process::ExitCode,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    arith::{self, Policy},
+    batch,
+    input::{self, Source},
+    output,
+    render::{Format, Picture},
+    trace::Tracer,
+    Result,
+};
+
+/// One of the two halves of a puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    /// The first part.
+    One,
+    /// The second part, unlocked by answering the first.
+    Two,
+}
+
+impl Part {
+    /// Both parts, in order.
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    /// The part with number `number`, 1 or 2.
+    pub fn from_number(number: u8) -> Option<Part> {
+        match number {
+            1 => Some(Part::One),
+            2 => Some(Part::Two),
+            _ => None,
+        }
+    }
+
+    /// The number of the part, 1 or 2.
+    pub fn number(&self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+/// The answer to one part of a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    /// A number.
+    Integer(i128),
+    /// Anything else, like a code or letters drawn on a screen.
+    Text(String),
+}
+
+macro_rules! impl_from_integer {
+    ($($integer:ty),*) => {
+        $(
+            impl From<$integer> for Answer {
+                fn from(value: $integer) -> Self {
+                    Answer::Integer(value as i128)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_integer!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, i128);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Integer(value) => write!(f, "{}", value),
+            Answer::Text(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+/// A day's puzzle: how to parse its input and how to answer both parts from it.
+pub trait Solution {
+    /// The year of the puzzle.
+    const YEAR: u16;
+    /// The day of the puzzle, from 1 to 25.
+    const DAY: u8;
+
+    /// The parsed puzzle input, which may borrow from the input text.
+    type Input<'a>;
+
+    /// Parses the puzzle input, rejecting it with where it is malformed.
+    fn parse(input: &str) -> Result<Self::Input<'_>>;
+
+    /// Answers part 1.
+    fn part1(input: &Self::Input<'_>) -> Result<Answer>;
+
+    /// Answers part 2.
+    fn part2(input: &Self::Input<'_>) -> Result<Answer>;
+
+    /// Answers `part`.
+    fn run(input: &Self::Input<'_>, part: Part) -> Result<Answer> {
+        match part {
+            Part::One => Self::part1(input),
+            Part::Two => Self::part2(input),
+        }
+    }
+
+    /// Like [`Solution::run`], but reports the steps of the simulation behind `part` to `tracer`.
+    /// Days with nothing to trace just answer.
+    fn run_traced(input: &Self::Input<'_>, part: Part, _tracer: &mut Tracer) -> Result<Answer> {
+        Self::run(input, part)
+    }
+
+    /// A picture of how `part` is answered, for puzzles that have one to show.
+    fn render(_input: &Self::Input<'_>, _part: Part) -> Result<Option<Picture>> {
+        Ok(None)
+    }
+}
+
+/// A type-erased [`Solution`], so that solutions of different days can be stored together.
+pub trait Solver: Sync {
+    /// The year of the puzzle.
+    fn year(&self) -> u16;
+
+    /// The day of the puzzle.
+    fn day(&self) -> u8;
+
+    /// Only parses `input`, to check that it's well-formed.
+    fn parse(&self, input: &str) -> Result<()>;
+
+    /// Parses `input` once and answers each of `parts` from it.
+    fn solve(&self, input: &str, parts: &[Part]) -> Result<Vec<Result<Answer>>>;
+
+    /// Like [`Solver::solve`], but also measures how long parsing and each part took.
+    fn solve_timed(&self, input: &str, parts: &[Part]) -> Result<(Vec<Result<Answer>>, Timings)>;
+
+    /// Parses `input` and renders `part` of it, or returns `None` if the day can't be rendered.
+    fn render(&self, input: &str, part: Part) -> Result<Option<Picture>>;
+}
+
+/// Time spent in each step of [`Solver::solve_timed`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Timings {
+    /// Time spent parsing.
+    pub parse: Duration,
+    /// One per part, in the order the parts were asked for.
+    pub parts: Vec<Duration>,
+}
+
+/// The [`Solver`] of solution `S`.
+pub struct Day<S>(PhantomData<fn() -> S>);
+
+impl<S> Day<S> {
+    /// The solver, to store along with the other days'.
+    pub const SOLVER: Day<S> = Day(PhantomData);
+}
+
+impl<S: Solution> Solver for Day<S> {
+    fn year(&self) -> u16 {
+        S::YEAR
+    }
+
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn parse(&self, input: &str) -> Result<()> {
+        S::parse(input).map(drop)
+    }
+
+    fn solve(&self, input: &str, parts: &[Part]) -> Result<Vec<Result<Answer>>> {
+        let input = S::parse(input)?;
+        Ok(parts.iter().map(|&part| S::run(&input, part)).collect())
+    }
+
+    fn solve_timed(&self, input: &str, parts: &[Part]) -> Result<(Vec<Result<Answer>>, Timings)> {
+        let start = Instant::now();
+        let input = S::parse(input)?;
+        let parse = start.elapsed();
+        let mut answers = Vec::new();
+        let mut durations = Vec::new();
+        for &part in parts {
+            let start = Instant::now();
+            answers.push(S::run(&input, part));
+            durations.push(start.elapsed());
+        }
+        let timings = Timings {
+            parse,
+            parts: durations,
+        };
+        Ok((answers, timings))
+    }
+
+    fn render(&self, input: &str, part: Part) -> Result<Option<Picture>> {
+        S::render(&S::parse(input)?, part)
+    }
+}
+
+/// Parses `input` and answers `part` of it.
+pub fn answer<S: Solution>(input: &str, part: Part) -> Result<Answer> {
+    S::run(&S::parse(input)?, part)
+}
+
+const DAY_USAGE: &str =
+    "Usage: <day> [--trace <PATH|->] [--json] [--arith <checked|saturating|wide>] \
+     [--render <ansi|svg>] [--part <1|2>] [<INPUT>|<DIR>|-]";
+
+/// The command line of a day binary.
+#[derive(Debug, Default)]
+struct DayArgs {
+    input: Option<String>,
+    /// Where to write the trace, `-` for stdout.
+    trace: Option<String>,
+    json: bool,
+    arith: Policy,
+    render: Option<Format>,
+    /// Only this part is answered or rendered, instead of both.
+    part: Option<Part>,
+}
+
+fn parse_day_args(mut args: impl Iterator<Item = String>) -> std::result::Result<DayArgs, String> {
+    let mut result = DayArgs::default();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--trace" => {
+                result.trace = Some(args.next().ok_or("Missing value for --trace")?);
+            }
+            "--json" => result.json = true,
+            "--arith" => {
+                let policy = args.next().ok_or("Missing value for --arith")?;
+                result.arith = Policy::from_name(&policy)
+                    .ok_or_else(|| format!("Invalid arithmetic policy: {}", policy))?;
+            }
+            "--render" => {
+                let format = args.next().ok_or("Missing value for --render")?;
+                result.render = Some(
+                    Format::from_name(&format)
+                        .ok_or_else(|| format!("Invalid render format: {}", format))?,
+                );
+            }
+            "--part" => {
+                let part = args.next().ok_or("Missing value for --part")?;
+                result.part = Some(
+                    part.parse()
+                        .ok()
+                        .and_then(Part::from_number)
+                        .ok_or_else(|| format!("Invalid part: {}", part))?,
+                );
+            }
+            "--help" | "-h" => return Err(DAY_USAGE.to_string()),
+            _ if result.input.is_none() => result.input = Some(arg),
+            _ => return Err(format!("Unexpected argument: {}\n{}", arg, DAY_USAGE)),
+        }
+    }
+    Ok(result)
+}
+
+/// Entry point of a day binary: reads the input named on the command line (or the crate's
+/// `src/input.txt`) and prints both answers. With `--trace`, the steps taken on the way are written
+/// to the given file, or to stdout for `-`. Given a directory, answers every input in it instead
+/// and prints a table. With `--json`, prints the answers as JSON records. `--arith` picks the
+/// [`Policy`] for answers that overflow. With `--render`, prints pictures of how the parts are
+/// answered instead of the answers. `--part` picks one part instead of both.
+pub fn main<S: Solution>(crate_dir: impl AsRef<Path>) -> ExitCode {
+    let DayArgs {
+        input,
+        trace,
+        json,
+        arith,
+        render,
+        part,
+    } = match parse_day_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(message) => {
+            eprintln!("{}", message);
+            return ExitCode::FAILURE;
+        }
+    };
+    arith::set_policy(arith);
+    let parts = match part {
+        Some(part) => vec![part],
+        None => Part::ALL.to_vec(),
+    };
+    let is_dir = input
+        .as_deref()
+        .is_some_and(|input| Path::new(input).is_dir());
+    if let Some(format) = render {
+        if json || is_dir || trace.is_some() {
+            eprintln!("--render needs a single input, and no --json or --trace");
+            return ExitCode::FAILURE;
+        }
+        if format == Format::Svg && parts.len() > 1 {
+            eprintln!("--render svg requires --part, as it writes a single document");
+            return ExitCode::FAILURE;
+        }
+    }
+    if json || is_dir {
+        if trace.is_some() {
+            eprintln!("--trace needs a single input and no --json");
+            return ExitCode::FAILURE;
+        }
+        let sources = match input.as_deref() {
+            Some(arg) => batch::sources(arg),
+            None => Ok(vec![Source::File(input::default_path(crate_dir))]),
+        };
+        return main_batch(&Day::<S>::SOLVER, sources, &parts, json);
+    }
+    let input = match input::from_arg(input.as_deref(), crate_dir) {
+        Ok(input) => input,
+        Err(error) => {
+            eprintln!("Failed to read {}", error);
+            return ExitCode::FAILURE;
+        }
+    };
+    let input = match S::parse(&input) {
+        Ok(input) => input,
+        Err(error) => {
+            eprintln!("{}", error);
+            return ExitCode::FAILURE;
+        }
+    };
+    if let Some(format) = render {
+        return main_render::<S>(&input, &parts, format);
+    }
+    let mut trace_out: Option<Box<dyn Write>> = match trace.as_deref() {
+        None => None,
+        Some("-") => Some(Box::new(io::stdout())),
+        Some(path) => match File::create(path) {
+            Ok(file) => Some(Box::new(BufWriter::new(file))),
+            Err(error) => {
+                eprintln!("Failed to create {}: {}", path, error);
+                return ExitCode::FAILURE;
+            }
+        },
+    };
+    let mut tracer = trace_out.as_mut().map(|out| Tracer::new(out.as_mut()));
+
+    let mut result = ExitCode::SUCCESS;
+    for &part in &parts {
+        let answer = match &mut tracer {
+            Some(tracer) => S::run_traced(&input, part, tracer),
+            None => S::run(&input, part),
+        };
+        match answer {
+            Ok(answer) => println!("Part {}: {}", part, answer),
+            Err(error) => {
+                eprintln!("Part {}: {}", part, error);
+                result = ExitCode::FAILURE;
+            }
+        }
+    }
+    if let Some(Err(error)) = tracer.map(Tracer::finish) {
+        eprintln!("Failed to write trace: {}", error);
+        result = ExitCode::FAILURE;
+    }
+    result
+}
+
+/// Prints a picture of each of `parts` in `format`.
+fn main_render<S: Solution>(input: &S::Input<'_>, parts: &[Part], format: Format) -> ExitCode {
+    let mut result = ExitCode::SUCCESS;
+    for &part in parts {
+        match S::render(input, part) {
+            Ok(Some(picture)) => print!("{}", picture.render(format)),
+            Ok(None) => {
+                eprintln!("Part {}: can't be rendered", part);
+                result = ExitCode::FAILURE;
+            }
+            Err(error) => {
+                eprintln!("Part {}: {}", part, error);
+                result = ExitCode::FAILURE;
+            }
+        }
+    }
+    result
+}
+
+/// Answers `parts` of every input in `sources` and prints them as a table, or as JSON records.
+fn main_batch(
+    solver: &dyn Solver,
+    sources: io::Result<Vec<Source>>,
+    parts: &[Part],
+    json: bool,
+) -> ExitCode {
+    let sources = match sources {
+        Ok(sources) => sources,
+        Err(error) => {
+            eprintln!("Failed to list inputs: {}", error);
+            return ExitCode::FAILURE;
+        }
+    };
+    let rows = batch::run(solver, &sources, parts);
+    if json {
+        let records = rows
+            .iter()
+            .flat_map(|row| row.records(solver, parts))
+            .collect::<Vec<_>>();
+        print!("{}", output::to_json(&records));
+    } else {
+        print!("{}", batch::table(&rows, parts));
+    }
+    if rows.iter().all(batch::Row::succeeded) {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
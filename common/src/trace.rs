@@ -0,0 +1,95 @@
+use std::{
+    fmt::Display,
+    io::{self, Write},
+};
+
+/// Receives the events a simulation emits as it steps.
+pub trait Observer<E> {
+    /// Called with every event, in the order they happen.
+    fn observe(&mut self, event: &E);
+}
+
+/// Ignores every event, for runs that aren't traced.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Ignore;
+
+impl<E> Observer<E> for Ignore {
+    fn observe(&mut self, _event: &E) {}
+}
+
+/// Records every event, in order.
+impl<E: Clone> Observer<E> for Vec<E> {
+    fn observe(&mut self, event: &E) {
+        self.push(event.clone());
+    }
+}
+
+/// Writes every event to `out` as `<step>: <event>`, numbering steps from 0, so that a run can
+/// be replayed from the output later.
+pub struct Tracer<'w> {
+    out: &'w mut dyn Write,
+    steps: usize,
+    error: Option<io::Error>,
+}
+
+impl<'w> Tracer<'w> {
+    /// A tracer writing to `out`, starting at step 0.
+    pub fn new(out: &'w mut dyn Write) -> Self {
+        Self {
+            out,
+            steps: 0,
+            error: None,
+        }
+    }
+
+    /// Flushes the output and returns the number of steps written, or the first error writing
+    /// them. Events after an error are counted but not written.
+    pub fn finish(self) -> io::Result<usize> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+        self.out.flush()?;
+        Ok(self.steps)
+    }
+}
+
+impl<E: Display> Observer<E> for Tracer<'_> {
+    fn observe(&mut self, event: &E) {
+        if self.error.is_none() {
+            if let Err(error) = writeln!(self.out, "{}: {}", self.steps, event) {
+                self.error = Some(error);
+            }
+        }
+        self.steps += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Counts down from `n`, emitting every number on the way.
+    fn count_down(mut n: u32, observer: &mut impl Observer<u32>) {
+        while n > 0 {
+            observer.observe(&n);
+            n -= 1;
+        }
+    }
+
+    #[test]
+    fn observers() {
+        count_down(3, &mut Ignore);
+        let mut events = vec![];
+        count_down(3, &mut events);
+        assert_eq!(events, [3, 2, 1]);
+    }
+
+    #[test]
+    fn tracer_numbers_steps() {
+        let mut out = vec![];
+        let mut tracer = Tracer::new(&mut out);
+        count_down(2, &mut tracer);
+        assert_eq!(tracer.finish().unwrap(), 2);
+        assert_eq!(String::from_utf8(out).unwrap(), "0: 2\n1: 1\n");
+    }
+}
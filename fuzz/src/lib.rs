@@ -0,0 +1,20 @@
+//! Fuzz targets for the parser of every day, one per day, named like `parse_2023_day_7`. Run one
+//! with the day's examples as seeds:
+//!
+//! ```sh
+//! cargo +nightly fuzz run parse_2023_day_7 fuzz/corpus/parse_2023_day_7 2023/day_7/fixtures
+//! ```
+
+#![warn(missing_docs)]
+
+use aoc_common::{Part, Solution};
+
+/// Parses `data` as the input of `S`, if it is UTF-8, and solves both parts of what parses. Either
+/// may fail, but must not panic.
+pub fn parse<S: Solution>(data: &[u8]) {
+    if let Ok(Ok(input)) = std::str::from_utf8(data).map(S::parse) {
+        for part in Part::ALL {
+            let _ = S::run(&input, part);
+        }
+    }
+}